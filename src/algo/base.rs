@@ -1,14 +1,42 @@
+extern crate rand;
+
 use std::rc::Rc;
 use std::cell::RefCell;
 
+use self::rand::{Rng, SeedableRng, XorShiftRng};
+
 use super::super::grid::Address;
 use super::super::maze::{OrthoMaze, WithinOrthoMaze};
 use super::super::highmap::OrthoHighMap;
 
 
+pub type Seed = u64;
+pub type MazeRng = XorShiftRng;
+
+
+// XorShift is used because, unlike `StdRng`, it gives the same sequence
+// on every platform, so a seed is enough to share a maze.
+pub fn seeded_rng(seed: Seed) -> MazeRng {
+    let mut rng = MazeRng::from_seed([
+        seed as u32,
+        (seed >> 32) as u32,
+        0x9E37_79B9,
+        0x7F4A_7C15
+    ]);
+
+    // close seeds give close first draws, run it a bit to spread them
+    for _ in 0..16 {
+        rng.next_u32();
+    }
+    rng
+}
+
+
+#[derive(Clone)]
 pub struct Args {
     pub maze: Rc<RefCell<OrthoMaze>>,
-    pub highmap: Rc<RefCell<OrthoHighMap>>
+    pub highmap: Rc<RefCell<OrthoHighMap>>,
+    pub rng: Rc<RefCell<MazeRng>>
 }
 
 
@@ -18,33 +46,137 @@ impl Address {
     }
     
     pub fn mark_active(&self, maze: &mut WithinOrthoMaze) {
-        self.from_mut(maze).map(|ref mut cell| cell.mark_active());
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_active();
+        }
     }
 
     pub fn unmark_active(&self, maze: &mut WithinOrthoMaze) {
-        self.from_mut(maze).map(|ref mut cell| cell.unmark_active());
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_active();
+        }
     }
 
     pub fn mark_current(&self, maze: &mut WithinOrthoMaze) {
-        self.from_mut(maze).map(|ref mut cell| cell.mark_current());
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_current();
+        }
     }
 
     pub fn unmark_current(&self, maze: &mut WithinOrthoMaze) {
-        self.from_mut(maze).map(|ref mut cell| cell.unmark_current());
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_current();
+        }
     }
 
     pub fn mark_visit(&self, maze: &mut WithinOrthoMaze) {
-        self.from_mut(maze).map(|ref mut cell| cell.mark_visit());
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_visit();
+        }
     }
 
     pub fn unmark_visit(&self, maze: &mut WithinOrthoMaze) {
-        self.from_mut(maze).map(|ref mut cell| cell.unmark_visit());
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_visit();
+        }
+    }
+
+    pub fn mark_path(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_path();
+        }
+    }
+
+    pub fn unmark_path(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_path();
+        }
+    }
+
+    pub fn mark_filled(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_filled();
+        }
+    }
+
+    pub fn unmark_filled(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_filled();
+        }
+    }
+
+    pub fn mark_frontier(&self, side: usize, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_frontier(side);
+        }
+    }
+
+    pub fn unmark_frontier(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_frontier();
+        }
+    }
+
+    pub fn mark_meeting(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_meeting();
+        }
+    }
+
+    pub fn unmark_meeting(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_meeting();
+        }
+    }
+
+    /// Adds a Trémaux mark on the passage between `self` and the neighbour
+    /// `to`.
+    pub fn add_passage_mark(&self, to: &Address, maze: &mut WithinOrthoMaze) {
+        if to.line == self.line && to.column == self.column + 1 {
+            if let Some(mut cell) = self.from_mut(maze) {
+                cell.add_mark_right();
+            }
+        } else if to.line == self.line && to.column + 1 == self.column {
+            if let Some(mut cell) = to.from_mut(maze) {
+                cell.add_mark_right();
+            }
+        } else if to.column == self.column && to.line == self.line + 1 {
+            if let Some(mut cell) = self.from_mut(maze) {
+                cell.add_mark_down();
+            }
+        } else if to.column == self.column && to.line + 1 == self.line {
+            if let Some(mut cell) = to.from_mut(maze) {
+                cell.add_mark_down();
+            }
+        }
+    }
+
+    pub fn clear_marks(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.clear_marks();
+        }
+    }
+
+    pub fn mark_group(&self, group: usize, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.mark_group(group);
+        }
+    }
+
+    pub fn unmark_group(&self, maze: &mut WithinOrthoMaze) {
+        if let Some(mut cell) = self.from_mut(maze) {
+            cell.unmark_group();
+        }
     }
 
     pub fn is_visited(&self, maze: &WithinOrthoMaze) -> bool {
         self.from(maze).map(|ref cell| cell.is_visited()).unwrap_or(false)
     }
 
+    pub fn is_active(&self, maze: &WithinOrthoMaze) -> bool {
+        self.from(maze).map(|ref cell| cell.is_active()).unwrap_or(false)
+    }
+
     pub fn is_on_right_border(&self, maze: &WithinOrthoMaze) -> bool {
         self.column + 1 == maze.grid().columns()
     }
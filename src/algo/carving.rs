@@ -1,12 +1,16 @@
 extern crate rand;
 
+use std;
 use std::borrow::Cow;
+use std::mem;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use self::rand::Rng;
 
 use super::super::grid::{Within, Address};
 use super::super::maze::{OrthoMaze, WithinOrthoMaze};
 use super::super::task::{Task, Status};
-use algo::base::Args;
+use algo::base::{Args, MazeRng, seeded_rng};
 
 
 impl Address {
@@ -21,23 +25,141 @@ impl Address {
     fn carve_to(&self, addr_to: &Address, maze: &mut OrthoMaze) {
         maze.carve(self.column, self.line, addr_to.column, addr_to.line);
     }
+
+    fn wall_right(&self, maze: &mut OrthoMaze) {
+        maze.wall(self.column, self.line, self.column + 1, self.line);
+    }
+
+    fn wall_down(&self, maze: &mut OrthoMaze) {
+        maze.wall(self.column, self.line, self.column, self.line + 1);
+    }
+
+    fn unvisited_neighbours(&self, maze: &OrthoMaze) -> Vec<Address> {
+        let pos = self.from(maze).expect("current position exists in maze");
+        pos.neighbours()
+            .into_iter()
+            .filter(|pos| !pos.is_visited())
+            .map(|pos| pos.into())
+            .collect()
+    }
+
+    fn visited_neighbours(&self, maze: &OrthoMaze) -> Vec<Address> {
+        let pos = self.from(maze).expect("current position exists in maze");
+        pos.neighbours()
+            .into_iter()
+            .filter(|pos| pos.is_visited())
+            .map(|pos| pos.into())
+            .collect()
+    }
 }
 
 
 // -----------------------------------------------------------------------------
 
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Corner {
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest
+}
+
+
+impl Corner {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Corner::NorthEast => "NorthEast",
+            Corner::NorthWest => "NorthWest",
+            Corner::SouthEast => "SouthEast",
+            Corner::SouthWest => "SouthWest"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Corner> {
+        match name.to_lowercase().as_str() {
+            "ne" | "northeast" => Some(Corner::NorthEast),
+            "nw" | "northwest" => Some(Corner::NorthWest),
+            "se" | "southeast" => Some(Corner::SouthEast),
+            "sw" | "southwest" => Some(Corner::SouthWest),
+            _ => None
+        }
+    }
+
+    pub fn next(&self) -> Corner {
+        match *self {
+            Corner::NorthEast => Corner::SouthEast,
+            Corner::SouthEast => Corner::SouthWest,
+            Corner::SouthWest => Corner::NorthWest,
+            Corner::NorthWest => Corner::NorthEast
+        }
+    }
+
+    fn is_east(&self) -> bool {
+        *self == Corner::NorthEast || *self == Corner::SouthEast
+    }
+
+    fn is_south(&self) -> bool {
+        *self == Corner::SouthEast || *self == Corner::SouthWest
+    }
+
+    fn vertical_name(&self) -> &'static str {
+        if self.is_south() { "down" } else { "up" }
+    }
+
+    fn horizontal_name(&self) -> &'static str {
+        if self.is_east() { "right" } else { "left" }
+    }
+
+    fn first_column(&self, maze: &WithinOrthoMaze) -> usize {
+        if self.is_east() { 0 } else { maze.grid().columns().saturating_sub(1) }
+    }
+
+    fn first(&self, maze: &WithinOrthoMaze) -> Address {
+        let line = if self.is_south() { 0 } else { maze.grid().lines().saturating_sub(1) };
+        Address { column: self.first_column(maze), line }
+    }
+
+    fn vertical_step(&self, addr: &Address, maze: &WithinOrthoMaze) -> Option<Address> {
+        let line = if self.is_south() { addr.line.checked_add(1) } else { addr.line.checked_sub(1) };
+        line.filter(|&line| line < maze.grid().lines())
+            .map(|line| Address { column: addr.column, line })
+    }
+
+    fn horizontal_step(&self, addr: &Address, maze: &WithinOrthoMaze) -> Option<Address> {
+        let column = if self.is_east() { addr.column.checked_add(1) } else { addr.column.checked_sub(1) };
+        column.filter(|&column| column < maze.grid().columns())
+            .map(|column| Address { column, line: addr.line })
+    }
+
+    fn walk(&self, addr: &Address, maze: &WithinOrthoMaze) -> Option<Address> {
+        self.horizontal_step(addr, maze)
+            .or_else(|| self.vertical_step(addr, maze)
+                     .map(|next| next.move_column(self.first_column(maze))))
+    }
+}
+
+
 pub struct BinaryTree {
     location: Address,
+    done: bool,
+    bias: f32,
+    corner: Corner,
     action: String
 }
 
 
 impl BinaryTree {
     pub fn new(maze: &WithinOrthoMaze) -> BinaryTree {
-        let location = maze.grid().crumbs().next().expect("first position exists");
+        BinaryTree::biased(maze, 0.5, Corner::SouthEast)
+    }
+
+    pub fn biased(maze: &WithinOrthoMaze, bias: f32, corner: Corner) -> BinaryTree {
         BinaryTree {
-            location,
+            location: corner.first(maze),
+            done: maze.grid().cell_count() == 0,
+            bias,
+            corner,
             action: String::new()
         }
     }
@@ -45,6 +167,18 @@ impl BinaryTree {
     fn log_action(&mut self, msg: &str) {
         self.action = format!("At {}, {}", self.location.to_str(), msg);
     }
+
+    fn walk(&mut self, maze: &mut OrthoMaze) {
+        self.location.unmark_current(maze);
+
+        match self.corner.walk(&self.location, &*maze) {
+            Some(next) => {
+                next.mark_current(maze);
+                self.location = next;
+            }
+            None => self.done = true
+        }
+    }
 }
 
 
@@ -60,28 +194,42 @@ impl Task<Args> for BinaryTree {
     fn execute_one(&mut self, args: &mut Args) -> Status {
         let mut maze = args.maze.borrow_mut();
 
-        if self.location.is_done_walking_right_then_down(&*maze) {
+        if self.done {
             return Status::Done;
         }
-        else if self.location.is_on_down_border(&*maze) {
-            self.log_action("Forced carve right");
-            self.location.carve_right(&mut *maze);
-        }
-        else if self.location.is_on_right_border(&*maze) {
-            self.log_action("Forced carve down");
-            self.location.carve_down(&mut *maze);
-        } else {
-            let vert = rand::thread_rng().next_f32() < 0.5;
-            if vert {
-                self.log_action("Carve down");
-                self.location.carve_down(&mut *maze);
-            } else {
-                self.log_action("Carve right");
-                self.location.carve_right(&mut *maze);
+
+        let vertical = self.corner.vertical_step(&self.location, &*maze);
+        let horizontal = self.corner.horizontal_step(&self.location, &*maze);
+
+        match (vertical, horizontal) {
+            (None, Some(next)) => {
+                let msg = format!("Forced carve {}", self.corner.horizontal_name());
+                self.log_action(&msg);
+                self.location.carve_to(&next, &mut maze);
+            }
+            (Some(next), None) => {
+                let msg = format!("Forced carve {}", self.corner.vertical_name());
+                self.log_action(&msg);
+                self.location.carve_to(&next, &mut maze);
+            }
+            (Some(vert_next), Some(hori_next)) => {
+                let vert = args.rng.borrow_mut().next_f32() < self.bias;
+                if vert {
+                    let msg = format!("Carve {}", self.corner.vertical_name());
+                    self.log_action(&msg);
+                    self.location.carve_to(&vert_next, &mut maze);
+                } else {
+                    let msg = format!("Carve {}", self.corner.horizontal_name());
+                    self.log_action(&msg);
+                    self.location.carve_to(&hori_next, &mut maze);
+                }
+            }
+            (None, None) => {
+                self.log_action("Corner reached, nothing to carve");
             }
         }
 
-        self.location.walk_right_then_down(&mut *maze);
+        self.walk(&mut maze);
         Status::Continuing
     }
 }
@@ -93,41 +241,73 @@ impl Task<Args> for BinaryTree {
 pub struct SideWinder {
     pub location: Address,
     start_x: usize,
+    done: bool,
+    bias: f32,
+    corner: Corner,
     action: String
 }
 
 
 impl SideWinder {
     pub fn new(maze: &WithinOrthoMaze) -> SideWinder {
-        let location = maze.grid().crumbs().next().expect("first position exists");
+        SideWinder::biased(maze, 0.5, Corner::SouthEast)
+    }
+
+    pub fn biased(maze: &WithinOrthoMaze, bias: f32, corner: Corner) -> SideWinder {
+        let location = corner.first(maze);
         let start_x = location.column;
-        SideWinder { location, action: String::new(), start_x }
+        SideWinder {
+            location,
+            start_x,
+            done: maze.grid().cell_count() == 0,
+            bias,
+            corner,
+            action: String::new()
+        }
     }
 
     fn log_action(&mut self, msg: &str) {
         self.action = format!("At {}, {}", self.location.to_str(), msg);
     }
 
-    fn close_group(&mut self, maze: &mut OrthoMaze) {
-        for column in self.start_x..self.location.column {
+    fn close_group(&mut self, maze: &mut OrthoMaze, rng: &mut MazeRng) {
+        let first = std::cmp::min(self.start_x, self.location.column);
+        let last = std::cmp::max(self.start_x, self.location.column);
+
+        for column in first..last + 1 {
             let location = self.location.move_column(column);
             location.unmark_active(maze);
         }
 
-        let door = rand::thread_rng().gen_range(
-            self.start_x, self.location.column + 1);
+        let door = rng.gen_range(first, last + 1);
 
         let location = self.location.move_column(door);
 
-        self.log_action(&format!("Close group, carve down at {}", location.to_str()));
+        self.log_action(&format!("Close group, carve {} at {}",
+                                 self.corner.vertical_name(), location.to_str()));
 
-        location.carve_down(maze);
+        if let Some(next) = self.corner.vertical_step(&location, &*maze) {
+            location.carve_to(&next, maze);
+        }
     }
 
-    fn continue_group(&mut self, maze: &mut OrthoMaze) {
+    fn continue_group(&mut self, next: &Address, maze: &mut OrthoMaze) {
         self.location.mark_active(maze);
-        self.log_action("Continue group, carve right");
-        self.location.carve_right(maze);
+        let msg = format!("Continue group, carve {}", self.corner.horizontal_name());
+        self.log_action(&msg);
+        self.location.carve_to(next, maze);
+    }
+
+    fn walk(&mut self, maze: &mut OrthoMaze) {
+        self.location.unmark_current(maze);
+
+        match self.corner.walk(&self.location, &*maze) {
+            Some(next) => {
+                next.mark_current(maze);
+                self.location = next;
+            }
+            None => self.done = true
+        }
     }
 }
 
@@ -143,38 +323,43 @@ impl Task<Args> for SideWinder {
 
     fn execute_one(&mut self, args: &mut Args) -> Status {
         let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
 
         let mut update_start = false;
 
-        if self.location.is_done_walking_right_then_down(&*maze) {
+        if self.done {
             return Status::Done;
         }
-        else if self.location.is_on_right_border(&*maze) {
-            self.close_group(&mut *maze);
-            update_start = true;
-        }
-        else if self.location.is_on_down_border(&*maze) {
-            self.continue_group(&mut *maze);
-        }
-        else {
-            use self::rand::Rng;
 
-            let build_group = rand::thread_rng().next_f32() < 0.5;
-            if build_group {
-                self.continue_group(&mut *maze);
-            } else {
-                self.close_group(&mut *maze);
+        let vertical = self.corner.vertical_step(&self.location, &*maze);
+        let horizontal = self.corner.horizontal_step(&self.location, &*maze);
+
+        match (vertical, horizontal) {
+            (_, None) => {
+                self.close_group(&mut maze, &mut rng);
                 update_start = true;
             }
+            (None, Some(next)) => {
+                self.continue_group(&next, &mut maze);
+            }
+            (Some(_), Some(next)) => {
+                let close = rng.next_f32() < self.bias;
+                if close {
+                    self.close_group(&mut maze, &mut rng);
+                    update_start = true;
+                } else {
+                    self.continue_group(&next, &mut maze);
+                }
+            }
         }
 
-        self.location.walk_right_then_down(&mut *maze);
+        self.walk(&mut maze);
 
         if update_start {
             self.start_x = self.location.column;
         }
 
-        if self.location.is_done_walking_right_then_down(&*maze) {
+        if self.done {
             Status::Done
         } else {
             Status::Continuing
@@ -240,12 +425,13 @@ impl AldousBroder {
         self.location = addr;
     }
 
-    fn pick_next(&mut self, maze: &OrthoMaze) -> Result<Address, &'static str> {
-        let pos = self.location.from(&*maze)
+    fn pick_next(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
+        -> Result<Address, &'static str> {
+        let pos = self.location.from(maze)
             .expect("current position exists in maze");
 
         let candidates = pos.neighbours();
-        let maybe_selected = rand::thread_rng().choose(&candidates);
+        let maybe_selected = rng.choose(&candidates);
 
         match maybe_selected {
             None => Err("impossible situation - no neighbours"),
@@ -266,13 +452,14 @@ impl Task<Args> for AldousBroder {
 
     fn execute_one(&mut self, args: &mut Args) -> Status {
         let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
 
         if self.restart_walk {
-            self.clear_walk(&mut *maze);
+            self.clear_walk(&mut maze);
             self.restart_walk = false;
         }
 
-        let next_addr = match self.pick_next(&*maze) {
+        let next_addr = match self.pick_next(&maze, &mut rng) {
             Err(msg) => {
                 self.log_action(msg);
                 return Status::Aborted(msg.to_owned());
@@ -292,11 +479,11 @@ impl Task<Args> for AldousBroder {
 
         self.restart_walk = !must_carve || self.walk.contains(&next_addr);
 
-        self.walk_to(next_addr, &mut *maze);
+        self.walk_to(next_addr, &mut maze);
 
         if maze.is_visitation_complete() {
             self.log_action("random walk ends, maze is complete");
-            self.clear_all(&mut *maze);
+            self.clear_all(&mut maze);
             Status::Done
         } else {
             Status::Continuing
@@ -361,19 +548,19 @@ impl Wilson {
             let dest = &pair[1];
             source.unmark_active(maze);
             source.mark_visit(maze);
-            source.carve_to(&dest, maze);
+            source.carve_to(dest, maze);
         }
 
         self.walk.clear();
     }
 
-    fn pick_next(&mut self, maze: &OrthoMaze)
+    fn pick_next(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
         -> Result<Address, &'static str> {
-            let pos = self.location.from(&*maze)
+            let pos = self.location.from(maze)
                 .expect("current position exists in maze");
 
             let candidates = pos.neighbours();
-            let maybe_selected = rand::thread_rng().choose(&candidates);
+            let maybe_selected = rng.choose(&candidates);
 
             match maybe_selected {
                 None => Err("impossible situation - no neighbours"),
@@ -381,9 +568,9 @@ impl Wilson {
             }
         }
 
-    fn pick_rand_unvisited(&mut self, maze: &OrthoMaze)
+    fn pick_rand_unvisited(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
         -> Result<Address, &'static str> {
-            maze.grid().anywhere_rand_match(|ref pos| !pos.is_visited())
+            maze.grid().anywhere_rand_match(rng, |pos| !pos.is_visited())
                 .map(|pos| pos.into())
                 .ok_or("impossible situation - no more unvisited cell")
         }
@@ -392,9 +579,9 @@ impl Wilson {
         let res;
         {
             let walk = &self.walk;
-            let mut iter = walk.into_iter();
+            let mut iter = walk.iter();
             res = iter.by_ref()
-                .take_while(|ref x| ***x != addr)
+                .take_while(|x| **x != addr)
                 .cloned()
                 .collect();
 
@@ -411,10 +598,10 @@ impl Wilson {
         self.location = addr;
     }
 
-    fn relocate_rand(&mut self, maze: &mut OrthoMaze) -> Status {
+    fn relocate_rand(&mut self, maze: &mut OrthoMaze, rng: &mut MazeRng) -> Status {
         self.location.unmark_current(&mut *maze);
 
-        self.location = match self.pick_rand_unvisited(&*maze) {
+        self.location = match self.pick_rand_unvisited(&*maze, rng) {
             Err(msg) => {
                 self.log_action(msg);
                 return Status::Aborted(msg.to_owned());
@@ -440,15 +627,16 @@ impl Task<Args> for Wilson {
 
     fn execute_one(&mut self, args: &mut Args) -> Status {
         let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
 
         if !self.started {
             self.started = true;
             self.location.mark_visit(&mut *maze);
             self.log_action("initialised");
-            return self.relocate_rand(&mut *maze);
+            return self.relocate_rand(&mut maze, &mut rng);
         }
 
-        let next_addr = match self.pick_next(&*maze) {
+        let next_addr = match self.pick_next(&maze, &mut rng) {
             Err(msg) => {
                 self.log_action(msg);
                 return Status::Aborted(msg.to_owned());
@@ -458,15 +646,15 @@ impl Task<Args> for Wilson {
 
         let next_is_visited = next_addr.is_visited(&*maze);
         if next_is_visited {
-            self.commit_walk(next_addr, &mut *maze);
+            self.commit_walk(next_addr, &mut maze);
 
             if maze.is_visitation_complete() {
                 self.log_action("current walk ended, maze is complete");
-                self.clear_all(&mut *maze);
+                self.clear_all(&mut maze);
                 Status::Done
             } else {
                 self.log_action("current walk ended, carve it, and select new unvisited cell");
-                self.relocate_rand(&mut *maze)
+                self.relocate_rand(&mut maze, &mut rng)
             }
         }
         else {
@@ -476,14 +664,1290 @@ impl Task<Args> for Wilson {
                 self.log_action(
                     &format!("loop detected at {}, rewind",
                              next_addr.to_str()));
-                self.rewind_to(next_addr, &mut *maze)
+                self.rewind_to(next_addr, &mut maze)
             }
             else {
                 self.log_action("walk continue");
-                self.walk_to(next_addr, &mut *maze)
+                self.walk_to(next_addr, &mut maze)
+            }
+
+            Status::Continuing
+        }
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+pub struct RecursiveBacktracker {
+    pub location: Address,
+    started: bool,
+    stack: Vec<Address>,
+    action: String
+}
+
+
+impl RecursiveBacktracker {
+    pub fn new(maze: &WithinOrthoMaze) -> RecursiveBacktracker {
+        let location = maze.grid().crumbs().next().expect("first position exists");
+        RecursiveBacktracker {
+            location,
+            started: false,
+            stack: Vec::new(),
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = format!("At {}, {}", self.location.to_str(), msg);
+    }
+
+    fn clear_visit(&self, maze: &mut OrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_visit(maze);
+        }
+    }
+
+    fn pick_rand_start(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
+        -> Result<Address, &'static str> {
+            maze.grid().anywhere_rand(rng)
+                .map(|pos| pos.into())
+                .ok_or("impossible situation - no cell to start from")
+        }
+
+    fn pick_next(&mut self, maze: &OrthoMaze, rng: &mut MazeRng) -> Option<Address> {
+        let candidates = self.location.unvisited_neighbours(maze);
+        rng.choose(&candidates).cloned()
+    }
+
+    fn push(&mut self, addr: Address, maze: &mut OrthoMaze) {
+        self.location.unmark_current(&mut *maze);
+
+        addr.mark_visit(&mut *maze);
+        addr.mark_active(&mut *maze);
+        addr.mark_current(&mut *maze);
+
+        self.stack.push(addr.clone());
+        self.location = addr;
+    }
+
+    fn backtrack(&mut self, maze: &mut OrthoMaze) -> Option<Address> {
+        if let Some(top) = self.stack.pop() {
+            top.unmark_current(&mut *maze);
+            top.unmark_active(&mut *maze);
+        }
+
+        let previous = self.stack.last().cloned();
+        if let Some(ref addr) = previous {
+            addr.mark_current(&mut *maze);
+            self.location = addr.clone();
+        }
+        previous
+    }
+}
+
+
+impl Task<Args> for RecursiveBacktracker {
+    fn name(&self) -> &'static str {
+        "RecursiveBacktracker"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if !self.started {
+            self.started = true;
+
+            let start = match self.pick_rand_start(&maze, &mut rng) {
+                Err(msg) => {
+                    self.log_action(msg);
+                    return Status::Aborted(msg.to_owned());
+                }
+                Ok(value) => value
+            };
+
+            self.push(start, &mut maze);
+            self.log_action("initialised");
+            return Status::Continuing;
+        }
+
+        if let Some(next_addr) = self.pick_next(&maze, &mut rng) {
+            self.log_action(&format!("carve to {}", next_addr.to_str()));
+            self.location.carve_to(&next_addr, &mut maze);
+            self.push(next_addr, &mut maze);
+            return Status::Continuing;
+        }
+
+        match self.backtrack(&mut maze) {
+            Some(_) => {
+                self.log_action("dead end, backtrack");
+                Status::Continuing
+            }
+            None => {
+                self.log_action("stack is empty, maze is complete");
+                self.clear_visit(&mut maze);
+                Status::Done
             }
+        }
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+pub struct HuntAndKill {
+    pub location: Address,
+    started: bool,
+    hunting: bool,
+    hunt_line: usize,
+    clean_line: bool,
+    walk: Vec<Address>,
+    action: String
+}
+
+
+impl HuntAndKill {
+    pub fn new(maze: &WithinOrthoMaze) -> HuntAndKill {
+        let location = maze.grid().crumbs().next().expect("first position exists");
+        HuntAndKill {
+            location,
+            started: false,
+            hunting: false,
+            hunt_line: 0,
+            clean_line: true,
+            walk: Vec::new(),
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = format!("At {}, {}", self.location.to_str(), msg);
+    }
+
+    fn clear_walk(&mut self, maze: &mut OrthoMaze) {
+        for addr in self.walk.iter() {
+            addr.unmark_active(&mut *maze);
+        }
+        self.walk.clear();
+    }
+
+    fn clear_visit(&self, maze: &mut OrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_visit(maze);
+        }
+    }
+
+    fn mark_line(&self, line: usize, maze: &mut OrthoMaze) {
+        for column in 0..maze.grid().columns() {
+            Address { column, line }.mark_active(maze);
+        }
+    }
+
+    fn unmark_line(&self, line: usize, maze: &mut OrthoMaze) {
+        for column in 0..maze.grid().columns() {
+            Address { column, line }.unmark_active(maze);
+        }
+    }
+
+    fn pick_rand_start(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
+        -> Result<Address, &'static str> {
+            maze.grid().anywhere_rand(rng)
+                .map(|pos| pos.into())
+                .ok_or("impossible situation - no cell to start from")
+        }
+
+    fn walk_to(&mut self, addr: Address, maze: &mut OrthoMaze) {
+        self.location.unmark_current(&mut *maze);
+
+        addr.mark_visit(&mut *maze);
+        addr.mark_active(&mut *maze);
+        addr.mark_current(&mut *maze);
+
+        self.walk.push(addr.clone());
+        self.location = addr;
+    }
+
+    fn start_hunt(&mut self, maze: &mut OrthoMaze) {
+        self.location.unmark_current(&mut *maze);
+        self.clear_walk(&mut *maze);
+
+        self.hunting = true;
+        self.clean_line = true;
+        self.location = Address { column: 0, line: self.hunt_line };
+
+        let line = self.location.line;
+        self.mark_line(line, &mut *maze);
+        self.location.mark_current(&mut *maze);
+    }
 
+    fn kill(&mut self, maze: &mut OrthoMaze, rng: &mut MazeRng) -> Status {
+        let candidates = self.location.unvisited_neighbours(&*maze);
+        let next_addr = rng.choose(&candidates).cloned();
+
+        if let Some(next_addr) = next_addr {
+            self.log_action(&format!("walk and carve to {}", next_addr.to_str()));
+            self.location.carve_to(&next_addr, &mut *maze);
+            self.walk_to(next_addr, &mut *maze);
+            Status::Continuing
+        } else if maze.is_visitation_complete() {
+            self.log_action("dead end, maze is complete");
+            self.location.unmark_current(&mut *maze);
+            self.clear_walk(&mut *maze);
+            self.clear_visit(&mut *maze);
+            Status::Done
+        } else {
+            self.log_action("dead end, start hunting");
+            self.start_hunt(&mut *maze);
             Status::Continuing
         }
     }
+
+    fn hunt(&mut self, maze: &mut OrthoMaze, rng: &mut MazeRng) -> Status {
+        let line = self.location.line;
+
+        if !self.location.is_visited(&*maze) {
+            let candidates = self.location.visited_neighbours(&*maze);
+            let maybe_selected = rng.choose(&candidates).cloned();
+
+            if let Some(prey) = maybe_selected {
+                self.log_action(&format!("prey found, carve to {}", prey.to_str()));
+                self.unmark_line(line, &mut *maze);
+                self.hunting = false;
+
+                self.location.carve_to(&prey, &mut *maze);
+                let start = self.location.clone();
+                self.walk_to(start, &mut *maze);
+                return Status::Continuing;
+            }
+
+            self.clean_line = false;
+        }
+
+        self.log_action("hunt");
+        self.location.walk_right_then_down(&mut *maze);
+
+        if self.location.line != line {
+            self.unmark_line(line, &mut *maze);
+
+            if self.clean_line && line == self.hunt_line {
+                self.hunt_line = line + 1;
+            }
+            self.clean_line = true;
+
+            if self.location.is_done_walking_right_then_down(&*maze) {
+                self.log_action("nothing left to hunt, maze is complete");
+                self.clear_visit(&mut *maze);
+                return Status::Done;
+            }
+
+            let line = self.location.line;
+            self.mark_line(line, &mut *maze);
+        }
+
+        Status::Continuing
+    }
+}
+
+
+impl Task<Args> for HuntAndKill {
+    fn name(&self) -> &'static str {
+        "HuntAndKill"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if !self.started {
+            self.started = true;
+
+            let start = match self.pick_rand_start(&maze, &mut rng) {
+                Err(msg) => {
+                    self.log_action(msg);
+                    return Status::Aborted(msg.to_owned());
+                }
+                Ok(value) => value
+            };
+
+            self.walk_to(start, &mut maze);
+            self.log_action("initialised");
+            return Status::Continuing;
+        }
+
+        if self.hunting {
+            self.hunt(&mut maze, &mut rng)
+        } else {
+            self.kill(&mut maze, &mut rng)
+        }
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+#[derive(Clone, Debug)]
+pub enum Selection {
+    Newest,
+    Oldest,
+    Random,
+    Mixed(Vec<(Selection, f32)>)
+}
+
+
+impl Default for Selection {
+    fn default() -> Selection {
+        Selection::Mixed(vec![
+            (Selection::Newest, 0.75),
+            (Selection::Random, 0.25)
+        ])
+    }
+}
+
+
+impl Selection {
+    fn pick(&self, count: usize, rng: &mut MazeRng) -> usize {
+        match *self {
+            Selection::Newest => count - 1,
+            Selection::Oldest => 0,
+            Selection::Random => rng.gen_range(0, count),
+            Selection::Mixed(ref choices) => {
+                let total = choices.iter().fold(0.0, |acc, &(_, weight)| acc + weight);
+                let mut roll = rng.next_f32() * total;
+
+                for &(ref selection, weight) in choices.iter() {
+                    if roll < weight {
+                        return selection.pick(count, rng);
+                    }
+                    roll -= weight;
+                }
+
+                choices.last()
+                    .map(|(selection, _)| selection.pick(count, rng))
+                    .unwrap_or(count - 1)
+            }
+        }
+    }
+}
+
+
+pub struct GrowingTree {
+    pub location: Address,
+    selection: Selection,
+    started: bool,
+    cells: Vec<Address>,
+    action: String
+}
+
+
+impl GrowingTree {
+    pub fn new(maze: &WithinOrthoMaze, selection: Selection) -> GrowingTree {
+        let location = maze.grid().crumbs().next().expect("first position exists");
+        GrowingTree {
+            location,
+            selection,
+            started: false,
+            cells: Vec::new(),
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = format!("At {}, {}", self.location.to_str(), msg);
+    }
+
+    fn clear_visit(&self, maze: &mut OrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_visit(maze);
+        }
+    }
+
+    fn pick_rand_start(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
+        -> Result<Address, &'static str> {
+            maze.grid().anywhere_rand(rng)
+                .map(|pos| pos.into())
+                .ok_or("impossible situation - no cell to start from")
+        }
+
+    fn add(&mut self, addr: Address, maze: &mut OrthoMaze) {
+        addr.mark_visit(&mut *maze);
+        addr.mark_active(&mut *maze);
+        self.cells.push(addr);
+    }
+
+    fn select(&mut self, index: usize, maze: &mut OrthoMaze) {
+        self.location.unmark_current(&mut *maze);
+        self.location = self.cells[index].clone();
+        self.location.mark_current(&mut *maze);
+    }
+}
+
+
+impl Task<Args> for GrowingTree {
+    fn name(&self) -> &'static str {
+        "GrowingTree"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if !self.started {
+            self.started = true;
+
+            let start = match self.pick_rand_start(&maze, &mut rng) {
+                Err(msg) => {
+                    self.log_action(msg);
+                    return Status::Aborted(msg.to_owned());
+                }
+                Ok(value) => value
+            };
+
+            self.add(start, &mut maze);
+            self.select(0, &mut maze);
+            self.log_action("initialised");
+            return Status::Continuing;
+        }
+
+        while !self.cells.is_empty() {
+            let index = self.selection.pick(self.cells.len(), &mut rng);
+            self.select(index, &mut maze);
+
+            let candidates = self.location.unvisited_neighbours(&maze);
+            if let Some(next_addr) = rng.choose(&candidates).cloned() {
+                self.log_action(&format!("carve to {}", next_addr.to_str()));
+                self.location.carve_to(&next_addr, &mut maze);
+                self.add(next_addr, &mut maze);
+                return Status::Continuing;
+            }
+
+            self.cells.remove(index);
+            self.location.unmark_active(&mut *maze);
+        }
+
+        self.log_action("no more cell to grow from, maze is complete");
+        self.location.unmark_current(&mut *maze);
+        self.clear_visit(&mut maze);
+        Status::Done
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>
+}
+
+
+impl DisjointSet {
+    fn new(count: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..count).collect(),
+            sizes: vec![1; count]
+        }
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut index = index;
+        while self.parents[index] != root {
+            let next = self.parents[index];
+            self.parents[index] = root;
+            index = next;
+        }
+
+        root
+    }
+
+    fn union(&mut self, first: usize, second: usize) -> (usize, usize) {
+        let first = self.find(first);
+        let second = self.find(second);
+
+        let (root, child) = if self.sizes[first] >= self.sizes[second] {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        (root, child)
+    }
+}
+
+
+pub struct Kruskal {
+    walls: Vec<(Address, Address)>,
+    sets: DisjointSet,
+    members: Vec<Vec<usize>>,
+    started: bool,
+    last_wall: Option<(Address, Address)>,
+    action: String
+}
+
+
+impl Kruskal {
+    pub fn new(maze: &WithinOrthoMaze) -> Kruskal {
+        let mut walls = Vec::new();
+        for addr in maze.grid().crumbs() {
+            if !addr.is_on_right_border(maze) {
+                let right = Address { column: addr.column + 1, line: addr.line };
+                walls.push((addr.clone(), right));
+            }
+            if !addr.is_on_down_border(maze) {
+                let down = Address { column: addr.column, line: addr.line + 1 };
+                walls.push((addr.clone(), down));
+            }
+        }
+
+        Kruskal {
+            walls,
+            sets: DisjointSet::new(maze.grid().cell_count()),
+            members: (0..maze.grid().cell_count()).map(|index| vec![index]).collect(),
+            started: false,
+            last_wall: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = msg.to_owned();
+    }
+
+    fn clear_all(&mut self, maze: &mut OrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_current(maze);
+            addr.unmark_group(maze);
+        }
+    }
+
+    // only the smaller set is repainted, and a lone root that never was
+    fn merge_members(&mut self, root: usize, child: usize, maze: &mut OrthoMaze) {
+        let mut moved = mem::take(&mut self.members[child]);
+        if self.members[root].len() == 1 {
+            moved.push(root);
+        }
+        for &index in &moved {
+            let (column, line) = maze.grid().pin(index);
+            Address { column, line }.mark_group(root, maze);
+        }
+        self.members[root].extend(moved.into_iter().filter(|&index| index != root));
+    }
+}
+
+
+impl Task<Args> for Kruskal {
+    fn name(&self) -> &'static str {
+        "Kruskal"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if !self.started {
+            self.started = true;
+            args.rng.borrow_mut().shuffle(&mut self.walls);
+            self.log_action("initialised, walls shuffled");
+            return Status::Continuing;
+        }
+
+        if let Some((first, second)) = self.last_wall.take() {
+            first.unmark_current(&mut *maze);
+            second.unmark_current(&mut *maze);
+        }
+
+        while let Some((first, second)) = self.walls.pop() {
+            let first_index = maze.grid().localize(first.column, first.line);
+            let second_index = maze.grid().localize(second.column, second.line);
+
+            if self.sets.find(first_index) == self.sets.find(second_index) {
+                continue;
+            }
+
+            self.log_action(&format!("merge sets of {} and {}",
+                                     first.to_str(), second.to_str()));
+
+            first.carve_to(&second, &mut maze);
+            let (root, child) = self.sets.union(first_index, second_index);
+            self.merge_members(root, child, &mut maze);
+
+            first.mark_current(&mut *maze);
+            second.mark_current(&mut *maze);
+            self.last_wall = Some((first, second));
+
+            return Status::Continuing;
+        }
+
+        self.log_action("no more walls, maze is complete");
+        self.clear_all(&mut maze);
+        Status::Done
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+pub struct SimplifiedPrim {
+    pub location: Address,
+    started: bool,
+    frontier: Vec<Address>,
+    action: String
+}
+
+
+impl SimplifiedPrim {
+    pub fn new(maze: &WithinOrthoMaze) -> SimplifiedPrim {
+        let location = maze.grid().crumbs().next().expect("first position exists");
+        SimplifiedPrim {
+            location,
+            started: false,
+            frontier: Vec::new(),
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = format!("At {}, {}", self.location.to_str(), msg);
+    }
+
+    fn clear_visit(&self, maze: &mut OrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_visit(maze);
+        }
+    }
+
+    fn pick_rand_start(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
+        -> Result<Address, &'static str> {
+            maze.grid().anywhere_rand(rng)
+                .map(|pos| pos.into())
+                .ok_or("impossible situation - no cell to start from")
+        }
+
+    fn join(&mut self, addr: Address, maze: &mut OrthoMaze) {
+        self.location.unmark_current(&mut *maze);
+
+        addr.unmark_active(&mut *maze);
+        addr.mark_visit(&mut *maze);
+        addr.mark_current(&mut *maze);
+
+        for neighbour in addr.unvisited_neighbours(&*maze) {
+            let in_frontier = neighbour.from(&*maze)
+                .map(|pos| pos.is_active())
+                .unwrap_or(false);
+
+            if !in_frontier {
+                neighbour.mark_active(&mut *maze);
+                self.frontier.push(neighbour);
+            }
+        }
+
+        self.location = addr;
+    }
+}
+
+
+impl Task<Args> for SimplifiedPrim {
+    fn name(&self) -> &'static str {
+        "SimplifiedPrim"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if !self.started {
+            self.started = true;
+
+            let start = match self.pick_rand_start(&maze, &mut rng) {
+                Err(msg) => {
+                    self.log_action(msg);
+                    return Status::Aborted(msg.to_owned());
+                }
+                Ok(value) => value
+            };
+
+            self.join(start, &mut maze);
+            self.log_action("initialised");
+            return Status::Continuing;
+        }
+
+        if self.frontier.is_empty() {
+            self.log_action("frontier is empty, maze is complete");
+            self.location.unmark_current(&mut *maze);
+            self.clear_visit(&mut maze);
+            return Status::Done;
+        }
+
+        let index = rng.gen_range(0, self.frontier.len());
+        let next_addr = self.frontier.swap_remove(index);
+
+        let candidates = next_addr.visited_neighbours(&maze);
+        let link = match rng.choose(&candidates) {
+            None => {
+                let msg = "impossible situation - frontier cell is not next to the maze";
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+            Some(value) => value.clone()
+        };
+
+        next_addr.carve_to(&link, &mut maze);
+        self.join(next_addr, &mut maze);
+        self.log_action(&format!("carve to {}", link.to_str()));
+        Status::Continuing
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+pub struct TruePrim {
+    pub location: Address,
+    started: bool,
+    costs: Vec<u32>,
+    frontier: BinaryHeap<(Reverse<u32>, usize)>,
+    action: String
+}
+
+
+impl TruePrim {
+    pub fn new(maze: &WithinOrthoMaze) -> TruePrim {
+        let location = maze.grid().crumbs().next().expect("first position exists");
+        TruePrim {
+            location,
+            started: false,
+            costs: Vec::new(),
+            frontier: BinaryHeap::new(),
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = format!("At {}, {}", self.location.to_str(), msg);
+    }
+
+    fn clear_visit(&self, maze: &mut OrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_visit(maze);
+        }
+    }
+
+    fn pick_rand_start(&mut self, maze: &OrthoMaze, rng: &mut MazeRng)
+        -> Result<Address, &'static str> {
+            maze.grid().anywhere_rand(rng)
+                .map(|pos| pos.into())
+                .ok_or("impossible situation - no cell to start from")
+        }
+
+    fn join(&mut self, addr: Address, maze: &mut OrthoMaze) {
+        self.location.unmark_current(&mut *maze);
+
+        addr.unmark_active(&mut *maze);
+        addr.mark_visit(&mut *maze);
+        addr.mark_current(&mut *maze);
+
+        for neighbour in addr.unvisited_neighbours(&*maze) {
+            let in_frontier = neighbour.from(&*maze)
+                .map(|pos| pos.is_active())
+                .unwrap_or(false);
+
+            if !in_frontier {
+                neighbour.mark_active(&mut *maze);
+                let index = maze.grid().localize(neighbour.column, neighbour.line);
+                self.frontier.push((Reverse(self.costs[index]), index));
+            }
+        }
+
+        self.location = addr;
+    }
+}
+
+
+impl Task<Args> for TruePrim {
+    fn name(&self) -> &'static str {
+        "TruePrim"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if !self.started {
+            self.started = true;
+
+            self.costs = (0..maze.grid().cell_count())
+                .map(|_| rng.gen_range(0, 100))
+                .collect();
+
+            let start = match self.pick_rand_start(&maze, &mut rng) {
+                Err(msg) => {
+                    self.log_action(msg);
+                    return Status::Aborted(msg.to_owned());
+                }
+                Ok(value) => value
+            };
+
+            self.join(start, &mut maze);
+            self.log_action("initialised, costs assigned");
+            return Status::Continuing;
+        }
+
+        let (cost, index) = match self.frontier.pop() {
+            None => {
+                self.log_action("frontier is empty, maze is complete");
+                self.location.unmark_current(&mut *maze);
+                self.clear_visit(&mut maze);
+                return Status::Done;
+            }
+            Some((Reverse(cost), index)) => (cost, index)
+        };
+
+        let (column, line) = maze.grid().pin(index);
+        let next_addr = Address { column, line };
+
+        let candidates = next_addr.visited_neighbours(&maze);
+        let link = match rng.choose(&candidates) {
+            None => {
+                let msg = "impossible situation - frontier cell is not next to the maze";
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+            Some(value) => value.clone()
+        };
+
+        next_addr.carve_to(&link, &mut maze);
+        self.join(next_addr, &mut maze);
+        self.log_action(&format!("cheapest cell costs {}, carve to {}",
+                                 cost, link.to_str()));
+        Status::Continuing
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+pub struct EllerRow {
+    pub right: Vec<bool>,
+    pub down: Vec<bool>
+}
+
+
+pub struct EllerRows {
+    sets: Vec<Option<usize>>,
+    next_set: usize,
+    rng: MazeRng
+}
+
+
+impl EllerRows {
+    pub fn new(width: usize, rng: MazeRng) -> EllerRows {
+        EllerRows {
+            sets: vec![None; width],
+            next_set: 0,
+            rng
+        }
+    }
+
+    fn assign_sets(&mut self) {
+        for set in self.sets.iter_mut() {
+            if set.is_none() {
+                *set = Some(self.next_set);
+                self.next_set = self.next_set.wrapping_add(1);
+            }
+        }
+    }
+
+    fn merge(&mut self, from: Option<usize>, into: Option<usize>) {
+        for set in self.sets.iter_mut() {
+            if *set == from {
+                *set = into;
+            }
+        }
+    }
+
+    fn join_right(&mut self, row: &mut EllerRow, column: usize) {
+        row.right[column] = true;
+        let (from, into) = (self.sets[column + 1], self.sets[column]);
+        self.merge(from, into);
+    }
+
+    pub fn next_row(&mut self) -> EllerRow {
+        let width = self.sets.len();
+        let mut row = EllerRow {
+            right: vec![false; width],
+            down: vec![false; width]
+        };
+        self.assign_sets();
+
+        for column in 0..width.saturating_sub(1) {
+            if self.sets[column] != self.sets[column + 1] && self.rng.gen() {
+                self.join_right(&mut row, column);
+            }
+        }
+
+        let mut below = vec![None; width];
+        for column in 0..width {
+            let set = self.sets[column];
+            if self.sets[..column].contains(&set) {
+                continue;
+            }
+
+            let members: Vec<usize> = (column..width)
+                .filter(|&other| self.sets[other] == set)
+                .collect();
+
+            let mut carved = false;
+            for &member in members.iter() {
+                if self.rng.gen() {
+                    row.down[member] = true;
+                    below[member] = set;
+                    carved = true;
+                }
+            }
+
+            if !carved {
+                let member = *self.rng.choose(&members).expect("set has members");
+                row.down[member] = true;
+                below[member] = set;
+            }
+        }
+
+        self.sets = below;
+        row
+    }
+
+    pub fn last_row(&mut self) -> EllerRow {
+        let width = self.sets.len();
+        let mut row = EllerRow {
+            right: vec![false; width],
+            down: vec![false; width]
+        };
+
+        self.assign_sets();
+
+        for column in 0..width.saturating_sub(1) {
+            if self.sets[column] != self.sets[column + 1] {
+                self.join_right(&mut row, column);
+            }
+        }
+
+        self.sets = vec![None; width];
+        row
+    }
+}
+
+
+impl Iterator for EllerRows {
+    type Item = EllerRow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_row())
+    }
+}
+
+
+pub struct Eller {
+    rows: Option<EllerRows>,
+    columns: usize,
+    line: usize,
+    endless: bool,
+    action: String
+}
+
+
+impl Eller {
+    pub fn new(maze: &WithinOrthoMaze) -> Eller {
+        Eller {
+            rows: None,
+            columns: maze.grid().columns(),
+            line: 0,
+            endless: false,
+            action: String::new()
+        }
+    }
+
+    pub fn endless(maze: &WithinOrthoMaze) -> Eller {
+        Eller {
+            endless: true,
+            .. Eller::new(maze)
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = format!("At line {}, {}", self.line, msg);
+    }
+
+    fn mark_line(&self, line: usize, maze: &mut OrthoMaze) {
+        for column in 0..maze.grid().columns() {
+            Address { column, line }.mark_active(maze);
+        }
+    }
+
+    fn unmark_line(&self, line: usize, maze: &mut OrthoMaze) {
+        for column in 0..maze.grid().columns() {
+            Address { column, line }.unmark_active(maze);
+        }
+    }
+}
+
+
+impl Task<Args> for Eller {
+    fn name(&self) -> &'static str {
+        "Eller"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if self.rows.is_none() {
+            let rng = seeded_rng(args.rng.borrow_mut().gen());
+            self.rows = Some(EllerRows::new(self.columns, rng));
+        }
+
+        if self.line > 0 {
+            let previous = self.line - 1;
+            self.unmark_line(previous, &mut maze);
+        }
+
+        if self.endless && self.line + 1 >= maze.grid().lines() {
+            maze.grow(1);
+            args.highmap.borrow_mut().grow(1);
+        }
+
+        let lines = maze.grid().lines();
+        if self.line >= lines {
+            self.log_action("no more line, maze is complete");
+            return Status::Done;
+        }
+
+        let is_last = self.line + 1 == lines;
+        let row = {
+            let rows = self.rows.as_mut().expect("rows are initialised");
+            if is_last { rows.last_row() } else { rows.next_row() }
+        };
+
+        let line = self.line;
+        for column in 0..maze.grid().columns() {
+            let addr = Address { column, line };
+            if row.right[column] {
+                addr.carve_right(&mut maze);
+            }
+            if row.down[column] {
+                addr.carve_down(&mut maze);
+            }
+        }
+
+        if is_last {
+            self.log_action("last line joins every set, maze is complete");
+            self.line += 1;
+            return Status::Done;
+        }
+
+        self.log_action("line carved");
+        self.mark_line(line, &mut maze);
+        self.line += 1;
+        Status::Continuing
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+
+
+#[derive(Clone, Debug)]
+struct Chamber {
+    column: usize,
+    line: usize,
+    columns: usize,
+    lines: usize
+}
+
+
+impl Chamber {
+    fn crumbs(&self) -> Vec<Address> {
+        let mut result = Vec::with_capacity(self.columns * self.lines);
+        for line in self.line..self.line + self.lines {
+            for column in self.column..self.column + self.columns {
+                result.push(Address { column, line });
+            }
+        }
+        result
+    }
+}
+
+
+pub struct RecursiveDivision {
+    chambers: Vec<Chamber>,
+    room_size: usize,
+    last_chamber: Option<Chamber>,
+    action: String
+}
+
+
+impl RecursiveDivision {
+    pub fn new(maze: &WithinOrthoMaze) -> RecursiveDivision {
+        RecursiveDivision::with_rooms(maze, 1)
+    }
+
+    pub fn with_rooms(maze: &WithinOrthoMaze, room_size: usize) -> RecursiveDivision {
+        let whole = Chamber {
+            column: 0,
+            line: 0,
+            columns: maze.grid().columns(),
+            lines: maze.grid().lines()
+        };
+        RecursiveDivision {
+            chambers: vec![whole],
+            room_size,
+            last_chamber: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = msg.to_owned();
+    }
+
+    fn is_divisible(&self, chamber: &Chamber) -> bool {
+        let is_room = chamber.columns <= self.room_size
+            && chamber.lines <= self.room_size;
+        chamber.columns >= 2 && chamber.lines >= 2 && !is_room
+    }
+
+    fn divide_horizontally(&mut self, chamber: &Chamber, maze: &mut OrthoMaze, rng: &mut MazeRng) {
+        let at = rng.gen_range(chamber.line, chamber.line + chamber.lines - 1);
+        let door = rng.gen_range(chamber.column, chamber.column + chamber.columns);
+
+        for column in chamber.column..chamber.column + chamber.columns {
+            if column != door {
+                Address { column, line: at }.wall_down(maze);
+            }
+        }
+
+        self.log_action(&format!("wall under line {}, door at {}",
+                                 at, Address { column: door, line: at }.to_str()));
+
+        let top_lines = at - chamber.line + 1;
+        self.chambers.push(Chamber { lines: top_lines, .. chamber.clone() });
+        self.chambers.push(Chamber {
+            line: at + 1,
+            lines: chamber.lines - top_lines,
+            .. chamber.clone()
+        });
+    }
+
+    fn divide_vertically(&mut self, chamber: &Chamber, maze: &mut OrthoMaze, rng: &mut MazeRng) {
+        let at = rng.gen_range(chamber.column, chamber.column + chamber.columns - 1);
+        let door = rng.gen_range(chamber.line, chamber.line + chamber.lines);
+
+        for line in chamber.line..chamber.line + chamber.lines {
+            if line != door {
+                Address { column: at, line }.wall_right(maze);
+            }
+        }
+
+        self.log_action(&format!("wall right of column {}, door at {}",
+                                 at, Address { column: at, line: door }.to_str()));
+
+        let left_columns = at - chamber.column + 1;
+        self.chambers.push(Chamber { columns: left_columns, .. chamber.clone() });
+        self.chambers.push(Chamber {
+            column: at + 1,
+            columns: chamber.columns - left_columns,
+            .. chamber.clone()
+        });
+    }
+}
+
+
+impl Task<Args> for RecursiveDivision {
+    fn name(&self) -> &'static str {
+        "RecursiveDivision"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if let Some(chamber) = self.last_chamber.take() {
+            for addr in chamber.crumbs() {
+                addr.unmark_active(&mut *maze);
+            }
+        }
+
+        while let Some(chamber) = self.chambers.pop() {
+            if !self.is_divisible(&chamber) {
+                continue;
+            }
+
+            let horizontal = if chamber.lines > chamber.columns {
+                true
+            } else if chamber.columns > chamber.lines {
+                false
+            } else {
+                rng.gen()
+            };
+
+            if horizontal {
+                self.divide_horizontally(&chamber, &mut maze, &mut rng);
+            } else {
+                self.divide_vertically(&chamber, &mut maze, &mut rng);
+            }
+
+            for addr in chamber.crumbs() {
+                addr.mark_active(&mut *maze);
+            }
+            self.last_chamber = Some(chamber);
+
+            return Status::Continuing;
+        }
+
+        self.log_action("no more chamber to divide, maze is complete");
+        Status::Done
+    }
 }
@@ -0,0 +1,221 @@
+use super::super::maze::{OrthoMaze, WithinOrthoMaze};
+use super::super::task::Task;
+use algo::base::Args;
+use algo::carving::{self, Corner, Selection};
+use algo::solving::{self, Hand, Heuristic};
+use grid::Address;
+
+
+#[derive(Clone)]
+pub enum Algo {
+    BinaryTree,
+    SideWinder,
+    AldousBroder,
+    Wilson,
+    RecursiveBacktracker,
+    HuntAndKill,
+    GrowingTree(Selection),
+    Kruskal,
+    SimplifiedPrim,
+    TruePrim,
+    Eller,
+    EllerEndless,
+    RecursiveDivision(usize)
+}
+
+
+impl Algo {
+    pub fn all() -> Vec<Algo> {
+        vec![
+            Algo::BinaryTree,
+            Algo::SideWinder,
+            Algo::AldousBroder,
+            Algo::Wilson,
+            Algo::RecursiveBacktracker,
+            Algo::HuntAndKill,
+            Algo::GrowingTree(Selection::default()),
+            Algo::Kruskal,
+            Algo::SimplifiedPrim,
+            Algo::TruePrim,
+            Algo::Eller,
+            Algo::EllerEndless,
+            Algo::RecursiveDivision(1)
+        ]
+    }
+
+    pub fn from_name(name: &str) -> Option<Algo> {
+        Algo::all().into_iter()
+            .find(|type_| type_.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Algo::BinaryTree => "BinaryTree",
+            Algo::SideWinder => "SideWinder",
+            Algo::AldousBroder => "AldousBroder",
+            Algo::Wilson => "Wilson",
+            Algo::RecursiveBacktracker => "RecursiveBacktracker",
+            Algo::HuntAndKill => "HuntAndKill",
+            Algo::GrowingTree(_) => "GrowingTree",
+            Algo::Kruskal => "Kruskal",
+            Algo::SimplifiedPrim => "SimplifiedPrim",
+            Algo::TruePrim => "TruePrim",
+            Algo::Eller => "Eller",
+            Algo::EllerEndless => "EllerEndless",
+            Algo::RecursiveDivision(_) => "RecursiveDivision"
+        }
+    }
+
+    /// Whether the algorithm keeps growing the maze instead of completing.
+    pub fn is_endless(&self) -> bool {
+        matches!(*self, Algo::EllerEndless)
+    }
+
+    pub fn blank_maze(&self, w: usize, h: usize) -> OrthoMaze {
+        match *self {
+            Algo::RecursiveDivision(_) => OrthoMaze::new_open(w, h),
+            _ => OrthoMaze::new(w, h)
+        }
+    }
+
+    pub fn create(&self, maze: &WithinOrthoMaze, tuning: &Tuning)
+        -> Box<Task<Args>> {
+            match *self {
+                Algo::BinaryTree => Box::new(carving::BinaryTree::biased(
+                        maze, tuning.bias, tuning.corner)),
+                Algo::SideWinder => Box::new(carving::SideWinder::biased(
+                        maze, tuning.bias, tuning.corner)),
+                Algo::AldousBroder => Box::new(carving::AldousBroder::new(maze)),
+                Algo::Wilson => Box::new(carving::Wilson::new(maze)),
+                Algo::RecursiveBacktracker =>
+                    Box::new(carving::RecursiveBacktracker::new(maze)),
+                Algo::HuntAndKill => Box::new(carving::HuntAndKill::new(maze)),
+                Algo::GrowingTree(ref selection) =>
+                    Box::new(carving::GrowingTree::new(maze, selection.clone())),
+                Algo::Kruskal => Box::new(carving::Kruskal::new(maze)),
+                Algo::SimplifiedPrim => Box::new(carving::SimplifiedPrim::new(maze)),
+                Algo::TruePrim => Box::new(carving::TruePrim::new(maze)),
+                Algo::Eller => Box::new(carving::Eller::new(maze)),
+                Algo::EllerEndless => Box::new(carving::Eller::endless(maze)),
+                Algo::RecursiveDivision(room_size) =>
+                    Box::new(carving::RecursiveDivision::with_rooms(maze, room_size))
+            }
+        }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+#[derive(Clone)]
+pub enum Solver {
+    BreadthFirst,
+    AStar(Heuristic),
+    WallFollower(Hand),
+    Tremaux,
+    DeadEndFilling,
+    CulDeSacFilling,
+    BidirectionalBreadthFirst
+}
+
+
+impl Solver {
+    pub fn all() -> Vec<Solver> {
+        let mut solvers = vec![Solver::BreadthFirst];
+        solvers.extend(Heuristic::all().into_iter().map(Solver::AStar));
+        solvers.extend(Hand::all().into_iter().map(Solver::WallFollower));
+        solvers.push(Solver::Tremaux);
+        solvers.push(Solver::DeadEndFilling);
+        solvers.push(Solver::CulDeSacFilling);
+        solvers.push(Solver::BidirectionalBreadthFirst);
+        solvers
+    }
+
+    pub fn from_name(name: &str) -> Option<Solver> {
+        Solver::all().into_iter()
+            .find(|type_| type_.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Solver::BreadthFirst => "BreadthFirst",
+            Solver::AStar(_) => "AStar",
+            Solver::WallFollower(_) => "WallFollower",
+            Solver::Tremaux => "Tremaux",
+            Solver::DeadEndFilling => "DeadEndFilling",
+            Solver::CulDeSacFilling => "CulDeSacFilling",
+            Solver::BidirectionalBreadthFirst => "BidirectionalBreadthFirst"
+        }
+    }
+
+    /// Name along with the settings telling apart solvers of the same name.
+    pub fn description(&self) -> String {
+        match *self {
+            Solver::AStar(heuristic) => format!("{} ({})", self.name(), heuristic.name()),
+            Solver::WallFollower(hand) => format!("{} ({} hand)", self.name(), hand.name()),
+            _ => self.name().to_owned()
+        }
+    }
+
+    /// The solver following this one in `all`.
+    pub fn next(&self) -> Solver {
+        let all = Solver::all();
+        let index = all.iter()
+            .position(|type_| type_.description() == self.description())
+            .unwrap_or(0);
+        all[(index + 1) % all.len()].clone()
+    }
+
+    pub fn create(&self, maze: &WithinOrthoMaze, start: Address, goal: Address)
+        -> Box<Task<Args>> {
+            match *self {
+                Solver::BreadthFirst =>
+                    Box::new(solving::BreadthFirst::new(maze, start, goal)),
+                Solver::AStar(heuristic) =>
+                    Box::new(solving::AStar::new(maze, start, goal, heuristic)),
+                Solver::WallFollower(hand) =>
+                    Box::new(solving::WallFollower::new(maze, start, goal, hand)),
+                Solver::Tremaux => Box::new(solving::Tremaux::new(start, goal)),
+                Solver::DeadEndFilling => Box::new(solving::DeadEndFilling::new(start, goal)),
+                Solver::CulDeSacFilling =>
+                    Box::new(solving::DeadEndFilling::with_cul_de_sacs(start, goal)),
+                Solver::BidirectionalBreadthFirst =>
+                    Box::new(solving::BidirectionalBreadthFirst::new(maze, start, goal))
+            }
+        }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+pub struct Tuning {
+    pub bias: f32,
+    pub corner: Corner
+}
+
+
+impl Default for Tuning {
+    fn default() -> Tuning {
+        Tuning::new()
+    }
+}
+
+
+impl Tuning {
+    pub fn new() -> Tuning {
+        Tuning {
+            bias: 0.5,
+            corner: Corner::SouthEast
+        }
+    }
+
+    pub fn change_bias(&mut self, delta: f32) {
+        let bias = (self.bias + delta).clamp(0.0, 1.0);
+        self.bias = (bias * 100.0).round() / 100.0;
+    }
+
+    pub fn next_corner(&mut self) {
+        self.corner = self.corner.next();
+    }
+}
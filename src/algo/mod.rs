@@ -1,4 +1,6 @@
 
 pub mod base;
+pub mod catalog;
 pub mod carving;
 pub mod seeding;
+pub mod solving;
@@ -9,7 +9,83 @@ use super::super::task::{Task, Status};
 use algo::base::Args;
 
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpawnLayout {
+    Center,
+    TopLeft,
+    Corners,
+    /// Three by three sources spread evenly over the maze.
+    Scattered
+}
+
+
+impl SpawnLayout {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SpawnLayout::Center => "Center",
+            SpawnLayout::TopLeft => "TopLeft",
+            SpawnLayout::Corners => "Corners",
+            SpawnLayout::Scattered => "Scattered"
+        }
+    }
+
+    pub fn next(&self) -> SpawnLayout {
+        match *self {
+            SpawnLayout::Center => SpawnLayout::TopLeft,
+            SpawnLayout::TopLeft => SpawnLayout::Corners,
+            SpawnLayout::Corners => SpawnLayout::Scattered,
+            SpawnLayout::Scattered => SpawnLayout::Center
+        }
+    }
+
+    /// Sources of a maze of `columns` by `lines` cells, without duplicates.
+    pub fn sources(&self, columns: usize, lines: usize) -> Vec<Address> {
+        if columns == 0 || lines == 0 {
+            return Vec::new();
+        }
+
+        let (last_column, last_line) = (columns - 1, lines - 1);
+        let cells = match *self {
+            SpawnLayout::Center => vec![(columns / 2, lines / 2)],
+            SpawnLayout::TopLeft => vec![(0, 0)],
+            SpawnLayout::Corners => vec![
+                (0, 0),
+                (last_column, 0),
+                (0, last_line),
+                (last_column, last_line)
+            ],
+            SpawnLayout::Scattered => {
+                let spread = |size: usize, part: usize| size * (2 * part + 1) / 6;
+                let mut cells = Vec::with_capacity(9);
+                for line in 0..3 {
+                    for column in 0..3 {
+                        cells.push((spread(columns, column), spread(lines, line)));
+                    }
+                }
+                cells
+            }
+        };
+
+        let mut sources : Vec<Address> = Vec::with_capacity(cells.len());
+        for (column, line) in cells {
+            let source = Address { column, line };
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        sources
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Measures the distance of every cell to its nearest source, walking the
+/// maze breadth first from all the sources at once. The highmap keeps the
+/// index of the source each cell is the closest to.
 pub struct DijkstraWalk {
+    sources: Vec<Address>,
     in_sight: VecDeque<Address>,
     action: String
 }
@@ -18,10 +94,17 @@ pub struct DijkstraWalk {
 impl DijkstraWalk {
     pub fn new(maze: &WithinOrthoMaze) -> DijkstraWalk {
         let start = maze.grid().center().expect("first position exists");
-        let mut in_sight = VecDeque::new();
-        in_sight.push_back(start.into());
+        DijkstraWalk::from_address(start.into())
+    }
+
+    pub fn from_address(start: Address) -> DijkstraWalk {
+        DijkstraWalk::from_sources(vec![start])
+    }
+
+    pub fn from_sources(sources: Vec<Address>) -> DijkstraWalk {
         DijkstraWalk {
-            in_sight,
+            in_sight: sources.iter().cloned().collect(),
+            sources,
             action: String::new()
         }
     }
@@ -46,7 +129,14 @@ impl Task<Args> for DijkstraWalk {
         let mut highmap = args.highmap.borrow_mut();
 
         if let Some(ref address) = self.in_sight.pop_front() {
-            let pos = address.from(&*maze).expect("address in maze exists");
+            let pos = match address.from(&*maze) {
+                Some(pos) => pos,
+                None => {
+                    let msg = "source outside of the maze";
+                    self.log_action(address, msg.to_owned());
+                    return Status::Aborted(msg.to_owned());
+                }
+            };
             let neighbours = pos.reachable_neighbours();
 
             let mut surroudings = Vec::with_capacity(4);
@@ -57,15 +147,19 @@ impl Task<Args> for DijkstraWalk {
                 
 
                 if let Some(height) = hpos.height() {
-                    surroudings.push(height);
+                    surroudings.push((height, hpos.source()));
                 } else if !self.in_sight.contains(&haddress) {
                     self.in_sight.push_back(haddress);
                 }
             }
 
-            let height = surroudings.iter().min()
-                .map(|x| x + 1)
-                .unwrap_or(0);
+            let (height, source) = match self.sources.iter().position(|src| src == address) {
+                Some(index) => (0, Some(index)),
+                None => surroudings.iter()
+                    .min_by_key(|&&(height, _)| height)
+                    .map(|&(height, source)| (height + 1, source))
+                    .unwrap_or((0, None))
+            };
 
             self.log_action(&address, format!("distance is {}", height));
 
@@ -73,6 +167,9 @@ impl Task<Args> for DijkstraWalk {
                 let mut hpos = address.from_mut(&mut *highmap)
                     .expect("position in highmap exists");
                 hpos.set_depth(height);
+                if let Some(source) = source {
+                    hpos.set_source(source);
+                }
             }
 
             highmap.highest = std::cmp::max(height, highmap.highest);
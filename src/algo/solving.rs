@@ -0,0 +1,1340 @@
+extern crate rand;
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use self::rand::Rng;
+
+
+use super::super::grid::{Grid, Address};
+use super::super::maze::WithinOrthoMaze;
+use super::super::task::{Task, Status};
+use algo::base::Args;
+
+
+/// Marks the cells of `path` once the exploration marks are cleared.
+fn clear_and_mark_path(path: &[Address], maze: &mut WithinOrthoMaze) {
+    for addr in maze.grid().crumbs() {
+        addr.unmark_current(maze);
+        addr.unmark_active(maze);
+        addr.unmark_visit(maze);
+    }
+
+    for addr in path {
+        addr.mark_path(maze);
+    }
+}
+
+
+/// Cells from the start to `goal`, following back how they were reached.
+fn trace_path(came_from: &Grid<Option<Address>>, goal: &Address) -> Vec<Address> {
+    let mut path = vec![goal.clone()];
+
+    while let Some(previous) = path.last()
+        .and_then(|addr| came_from.at(addr.column, addr.line))
+        .and_then(|previous| previous.clone())
+    {
+        path.push(previous);
+    }
+
+    path.reverse();
+    path
+}
+
+
+/// Removes what a previous solver left: its path, its passage marks, its
+/// filled cells and where its searches met.
+fn clear_path(maze: &mut WithinOrthoMaze) {
+    for addr in maze.grid().crumbs() {
+        addr.unmark_path(maze);
+        addr.clear_marks(maze);
+        addr.unmark_filled(maze);
+        addr.unmark_meeting(maze);
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Heuristic {
+    Manhattan,
+    Euclidean,
+    /// Estimates nothing, A* then explores like Dijkstra's algorithm.
+    Zero
+}
+
+
+impl Heuristic {
+    pub fn all() -> Vec<Heuristic> {
+        vec![Heuristic::Manhattan, Heuristic::Euclidean, Heuristic::Zero]
+    }
+
+    pub fn from_name(name: &str) -> Option<Heuristic> {
+        Heuristic::all().into_iter()
+            .find(|heuristic| heuristic.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Heuristic::Manhattan => "Manhattan",
+            Heuristic::Euclidean => "Euclidean",
+            Heuristic::Zero => "Zero"
+        }
+    }
+
+    pub fn next(&self) -> Heuristic {
+        match *self {
+            Heuristic::Manhattan => Heuristic::Euclidean,
+            Heuristic::Euclidean => Heuristic::Zero,
+            Heuristic::Zero => Heuristic::Manhattan
+        }
+    }
+
+    /// Estimated number of steps from `from` to `to`, never more than the
+    /// real distance.
+    pub fn estimate(&self, from: &Address, to: &Address) -> f64 {
+        let dx = (from.column as f64 - to.column as f64).abs();
+        let dy = (from.line as f64 - to.line as f64).abs();
+
+        match *self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Zero => 0.0
+        }
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Shortest path between two cells, found by exploring the maze breadth
+/// first from `start` until `goal` is reached.
+pub struct BreadthFirst {
+    start: Address,
+    goal: Address,
+    frontier: VecDeque<Address>,
+    came_from: Grid<Option<Address>>,
+    location: Option<Address>,
+    action: String
+}
+
+
+impl BreadthFirst {
+    pub fn new(maze: &WithinOrthoMaze, start: Address, goal: Address) -> BreadthFirst {
+        let mut frontier = VecDeque::new();
+        frontier.push_back(start.clone());
+
+        BreadthFirst {
+            start,
+            goal,
+            frontier,
+            came_from: Grid::new(maze.grid().columns(), maze.grid().lines()),
+            location: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = match self.location {
+            Some(ref location) => format!("At {}, {}", location.to_str(), msg),
+            None => msg.to_owned()
+        };
+    }
+
+    fn start(&mut self, maze: &mut WithinOrthoMaze) -> Result<(), &'static str> {
+        if self.start.from(maze).is_none() || self.goal.from(maze).is_none() {
+            return Err("start or goal outside of the maze");
+        }
+
+        clear_path(maze);
+        self.start.mark_visit(maze);
+        Ok(())
+    }
+
+    fn walk_to(&mut self, addr: &Address, maze: &mut WithinOrthoMaze) {
+        if let Some(ref location) = self.location {
+            location.unmark_current(maze);
+        }
+        addr.unmark_active(maze);
+        addr.mark_current(maze);
+        self.location = Some(addr.clone());
+    }
+}
+
+
+impl Task<Args> for BreadthFirst {
+    fn name(&self) -> &'static str {
+        "BreadthFirst"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if self.location.is_none() {
+            if let Err(msg) = self.start(&mut *maze) {
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        }
+
+        let address = match self.frontier.pop_front() {
+            Some(address) => address,
+            None => {
+                let msg = "goal cannot be reached from start";
+                clear_and_mark_path(&[], &mut *maze);
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        };
+
+        self.walk_to(&address, &mut *maze);
+
+        if address == self.goal {
+            let path = trace_path(&self.came_from, &self.goal);
+            clear_and_mark_path(&path, &mut *maze);
+
+            let msg = format!("goal reached in {} steps", path.len() - 1);
+            self.log_action(&msg);
+            return Status::Done;
+        }
+
+        let neighbours : Vec<Address> = {
+            let pos = address.from(&*maze).expect("address in maze exists");
+            pos.reachable_neighbours()
+                .into_iter()
+                .map(|neighbour| neighbour.into())
+                .filter(|neighbour: &Address| !neighbour.is_visited(&*maze))
+                .collect()
+        };
+
+        for neighbour in neighbours {
+            neighbour.mark_visit(&mut *maze);
+            neighbour.mark_active(&mut *maze);
+            if let Some(previous) = self.came_from.at_mut(neighbour.column, neighbour.line) {
+                *previous = Some(address.clone());
+            }
+            self.frontier.push_back(neighbour);
+        }
+
+        let msg = format!("{} cells in sight", self.frontier.len());
+        self.log_action(&msg);
+        Status::Continuing
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Shortest path between two cells, exploring first the cells of the open
+/// set which look the closest to `goal` according to `heuristic`.
+pub struct AStar {
+    start: Address,
+    goal: Address,
+    heuristic: Heuristic,
+    open: Vec<Address>,
+    cost: Grid<Option<usize>>,
+    came_from: Grid<Option<Address>>,
+    location: Option<Address>,
+    action: String
+}
+
+
+impl AStar {
+    pub fn new(maze: &WithinOrthoMaze, start: Address, goal: Address, heuristic: Heuristic)
+        -> AStar
+    {
+        let columns = maze.grid().columns();
+        let lines = maze.grid().lines();
+
+        let mut cost = Grid::new(columns, lines);
+        if let Some(start_cost) = cost.at_mut(start.column, start.line) {
+            *start_cost = Some(0);
+        }
+
+        AStar {
+            open: vec![start.clone()],
+            start,
+            goal,
+            heuristic,
+            cost,
+            came_from: Grid::new(columns, lines),
+            location: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = match self.location {
+            Some(ref location) => format!("At {}, {}", location.to_str(), msg),
+            None => msg.to_owned()
+        };
+    }
+
+    fn start(&mut self, maze: &mut WithinOrthoMaze) -> Result<(), &'static str> {
+        if self.start.from(maze).is_none() || self.goal.from(maze).is_none() {
+            return Err("start or goal outside of the maze");
+        }
+
+        clear_path(maze);
+        self.start.mark_active(maze);
+        Ok(())
+    }
+
+    fn cost_of(&self, addr: &Address) -> usize {
+        self.cost.at(addr.column, addr.line)
+            .and_then(|cost| *cost)
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Takes out of the open set the cell of lowest estimated total cost,
+    /// ties going to the one closest to the goal.
+    fn pop_best(&mut self) -> Option<Address> {
+        let mut best : Option<(usize, f64, f64)> = None;
+
+        for (index, addr) in self.open.iter().enumerate() {
+            let estimate = self.heuristic.estimate(addr, &self.goal);
+            let total = self.cost_of(addr) as f64 + estimate;
+
+            let better = match best {
+                Some((_, best_total, best_estimate)) =>
+                    total < best_total || (total == best_total && estimate < best_estimate),
+                None => true
+            };
+            if better {
+                best = Some((index, total, estimate));
+            }
+        }
+
+        best.map(|(index, _, _)| self.open.swap_remove(index))
+    }
+
+    fn walk_to(&mut self, addr: &Address, maze: &mut WithinOrthoMaze) {
+        if let Some(ref location) = self.location {
+            location.unmark_current(maze);
+        }
+        addr.unmark_active(maze);
+        addr.mark_visit(maze);
+        addr.mark_current(maze);
+        self.location = Some(addr.clone());
+    }
+}
+
+
+impl Task<Args> for AStar {
+    fn name(&self) -> &'static str {
+        "AStar"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if self.location.is_none() {
+            if let Err(msg) = self.start(&mut *maze) {
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        }
+
+        let address = match self.pop_best() {
+            Some(address) => address,
+            None => {
+                let msg = "goal cannot be reached from start";
+                clear_and_mark_path(&[], &mut *maze);
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        };
+
+        self.walk_to(&address, &mut *maze);
+
+        if address == self.goal {
+            let path = trace_path(&self.came_from, &self.goal);
+            clear_and_mark_path(&path, &mut *maze);
+
+            let msg = format!("goal reached in {} steps", path.len() - 1);
+            self.log_action(&msg);
+            return Status::Done;
+        }
+
+        let cost = self.cost_of(&address) + 1;
+        let neighbours : Vec<Address> = {
+            let pos = address.from(&*maze).expect("address in maze exists");
+            pos.reachable_neighbours()
+                .into_iter()
+                .map(|neighbour| neighbour.into())
+                .filter(|neighbour: &Address| !neighbour.is_visited(&*maze))
+                .collect()
+        };
+
+        for neighbour in neighbours {
+            if cost >= self.cost_of(&neighbour) {
+                continue;
+            }
+
+            if let Some(neighbour_cost) = self.cost.at_mut(neighbour.column, neighbour.line) {
+                *neighbour_cost = Some(cost);
+            }
+            if let Some(previous) = self.came_from.at_mut(neighbour.column, neighbour.line) {
+                *previous = Some(address.clone());
+            }
+            if !self.open.contains(&neighbour) {
+                neighbour.mark_active(&mut *maze);
+                self.open.push(neighbour);
+            }
+        }
+
+        let msg = format!("cost is {}, {} cells open", cost - 1, self.open.len());
+        self.log_action(&msg);
+        Status::Continuing
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Heading {
+    North,
+    East,
+    South,
+    West
+}
+
+
+impl Heading {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Heading::North => "north",
+            Heading::East => "east",
+            Heading::South => "south",
+            Heading::West => "west"
+        }
+    }
+
+    pub fn left(&self) -> Heading {
+        match *self {
+            Heading::North => Heading::West,
+            Heading::West => Heading::South,
+            Heading::South => Heading::East,
+            Heading::East => Heading::North
+        }
+    }
+
+    pub fn right(&self) -> Heading {
+        self.left().back()
+    }
+
+    pub fn back(&self) -> Heading {
+        self.left().left()
+    }
+
+    /// Address one step from `addr` toward the heading, if the grid has
+    /// room for it.
+    pub fn step(&self, addr: &Address) -> Option<Address> {
+        let (column, line) = match *self {
+            Heading::North => (Some(addr.column), addr.line.checked_sub(1)),
+            Heading::East => (addr.column.checked_add(1), Some(addr.line)),
+            Heading::South => (Some(addr.column), addr.line.checked_add(1)),
+            Heading::West => (addr.column.checked_sub(1), Some(addr.line))
+        };
+
+        match (column, line) {
+            (Some(column), Some(line)) => Some(Address { column, line }),
+            _ => None
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        match *self {
+            Heading::North => 0b0001,
+            Heading::East => 0b0010,
+            Heading::South => 0b0100,
+            Heading::West => 0b1000
+        }
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hand {
+    Left,
+    Right
+}
+
+
+impl Hand {
+    pub fn all() -> Vec<Hand> {
+        vec![Hand::Left, Hand::Right]
+    }
+
+    pub fn from_name(name: &str) -> Option<Hand> {
+        Hand::all().into_iter()
+            .find(|hand| hand.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Hand::Left => "Left",
+            Hand::Right => "Right"
+        }
+    }
+
+    /// Headings to try from `heading`, the wall side first.
+    fn turns(&self, heading: Heading) -> [Heading; 4] {
+        match *self {
+            Hand::Left => [heading.left(), heading, heading.right(), heading.back()],
+            Hand::Right => [heading.right(), heading, heading.left(), heading.back()]
+        }
+    }
+}
+
+
+/// Walks from `start` keeping a hand on the wall until `goal` is reached,
+/// aborting once it enters a cell again with the same heading.
+pub struct WallFollower {
+    start: Address,
+    goal: Address,
+    hand: Hand,
+    heading: Heading,
+    headings: Grid<u8>,
+    trail: Vec<Address>,
+    location: Option<Address>,
+    action: String
+}
+
+
+impl WallFollower {
+    pub fn new(maze: &WithinOrthoMaze, start: Address, goal: Address, hand: Hand)
+        -> WallFollower
+    {
+        WallFollower {
+            start,
+            goal,
+            hand,
+            heading: Heading::East,
+            headings: Grid::new(maze.grid().columns(), maze.grid().lines()),
+            trail: Vec::new(),
+            location: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = match self.location {
+            Some(ref location) => format!("At {}, {}", location.to_str(), msg),
+            None => msg.to_owned()
+        };
+    }
+
+    fn start(&mut self, maze: &mut WithinOrthoMaze) -> Result<(), &'static str> {
+        if self.start.from(maze).is_none() || self.goal.from(maze).is_none() {
+            return Err("start or goal outside of the maze");
+        }
+
+        clear_path(maze);
+
+        let start = self.start.clone();
+        self.trail.push(start.clone());
+        self.walk_to(&start, maze);
+        Ok(())
+    }
+
+    /// First heading along the hand that leads to an open neighbour.
+    fn pick_heading(&self, maze: &WithinOrthoMaze) -> Option<(Heading, Address)> {
+        let location = self.location.as_ref()?;
+        let pos = location.from(maze)?;
+
+        self.hand.turns(self.heading).iter()
+            .filter_map(|heading| heading.step(location).map(|next| (*heading, next)))
+            .find(|(_, next)| {
+                next.from(maze)
+                    .map(|next_pos| pos.can_move_to(&next_pos))
+                    .unwrap_or(false)
+            })
+    }
+
+    /// Tells if the cell was already entered with the current heading,
+    /// recording it otherwise.
+    fn is_circling(&mut self, addr: &Address) -> bool {
+        let bit = self.heading.bit();
+        match self.headings.at_mut(addr.column, addr.line) {
+            Some(headings) if *headings & bit != 0 => true,
+            Some(headings) => {
+                *headings |= bit;
+                false
+            }
+            None => false
+        }
+    }
+
+    fn walk_to(&mut self, addr: &Address, maze: &mut WithinOrthoMaze) {
+        if let Some(ref location) = self.location {
+            location.unmark_current(maze);
+        }
+        addr.mark_visit(maze);
+        addr.mark_current(maze);
+        self.location = Some(addr.clone());
+    }
+
+    /// Extends the trail up to `addr`, cutting the detour when the walker
+    /// comes back to a cell of the trail.
+    fn extend_trail(&mut self, addr: &Address, maze: &mut WithinOrthoMaze) {
+        if let Some(index) = self.trail.iter().position(|step| step == addr) {
+            for step in self.trail.drain(index + 1..) {
+                step.unmark_active(maze);
+            }
+        } else {
+            addr.mark_active(maze);
+            self.trail.push(addr.clone());
+        }
+    }
+}
+
+
+impl Task<Args> for WallFollower {
+    fn name(&self) -> &'static str {
+        "WallFollower"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if self.location.is_none() {
+            if let Err(msg) = self.start(&mut *maze) {
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        }
+
+        if self.location.as_ref() == Some(&self.goal) {
+            let path = self.trail.clone();
+            clear_and_mark_path(&path, &mut *maze);
+
+            let msg = format!("goal reached, path of {} steps", path.len() - 1);
+            self.log_action(&msg);
+            return Status::Done;
+        }
+
+        let (heading, next) = match self.pick_heading(&*maze) {
+            Some(step) => step,
+            None => {
+                let msg = "start is walled in, goal cannot be reached";
+                clear_and_mark_path(&[], &mut *maze);
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        };
+
+        self.heading = heading;
+        if self.is_circling(&next) {
+            let msg = "circling a loop, goal is not along the walls of start";
+            clear_and_mark_path(&[], &mut *maze);
+            self.log_action(msg);
+            return Status::Aborted(msg.to_owned());
+        }
+
+        self.extend_trail(&next, &mut *maze);
+        self.walk_to(&next, &mut *maze);
+
+        let msg = format!("heading {}", heading.name());
+        self.log_action(&msg);
+        Status::Continuing
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Trémaux's algorithm, marking the passages it walks through. A passage
+/// with two marks is never taken again.
+pub struct Tremaux {
+    start: Address,
+    goal: Address,
+    previous: Option<Address>,
+    arrived_on_new: bool,
+    location: Option<Address>,
+    action: String
+}
+
+
+impl Tremaux {
+    pub fn new(start: Address, goal: Address) -> Tremaux {
+        Tremaux {
+            start,
+            goal,
+            previous: None,
+            arrived_on_new: true,
+            location: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = match self.location {
+            Some(ref location) => format!("At {}, {}", location.to_str(), msg),
+            None => msg.to_owned()
+        };
+    }
+
+    fn start(&mut self, maze: &mut WithinOrthoMaze) -> Result<(), &'static str> {
+        if self.start.from(maze).is_none() || self.goal.from(maze).is_none() {
+            return Err("start or goal outside of the maze");
+        }
+
+        clear_path(maze);
+
+        let start = self.start.clone();
+        self.walk_to(&start, maze);
+        Ok(())
+    }
+
+    /// Passages of the current cell with their mark count.
+    fn passages(&self, maze: &WithinOrthoMaze) -> Vec<(Address, u8)> {
+        let location = self.location.as_ref().expect("walker has a location");
+        let pos = location.from(maze).expect("address in maze exists");
+
+        pos.reachable_neighbours()
+            .iter()
+            .map(|neighbour| (neighbour.into(), pos.marks_to(neighbour)))
+            .collect()
+    }
+
+    /// Picks the next cell following Trémaux's rules.
+    fn pick_next<R: Rng>(&self, maze: &WithinOrthoMaze, rng: &mut R)
+        -> Result<(Address, &'static str), &'static str>
+    {
+        let passages = self.passages(maze);
+        let came_back_by = passages.iter()
+            .find(|(addr, _)| Some(addr) == self.previous.as_ref());
+
+        // reaching a known cell through a new passage: a loop, go back
+        if let Some((previous, 1)) = came_back_by {
+            if !self.arrived_on_new {
+                return Ok((previous.clone(), "known cell, turn back"));
+            }
+        }
+
+        let unmarked : Vec<Address> = passages.iter()
+            .filter(|(_, marks)| *marks == 0)
+            .map(|(addr, _)| addr.clone())
+            .collect();
+        if let Some(next) = rng.choose(&unmarked) {
+            return Ok((next.clone(), "take a new passage"));
+        }
+
+        passages.iter()
+            .find(|(_, marks)| *marks == 1)
+            .map(|(addr, _)| (addr.clone(), "no new passage, go back"))
+            .ok_or("every passage is marked twice, goal cannot be reached")
+    }
+
+    fn walk_to(&mut self, addr: &Address, maze: &mut WithinOrthoMaze) {
+        if let Some(ref location) = self.location {
+            location.unmark_current(maze);
+        }
+        self.arrived_on_new = !addr.is_visited(maze);
+        addr.mark_visit(maze);
+        addr.mark_current(maze);
+        self.previous = self.location.take();
+        self.location = Some(addr.clone());
+    }
+
+    /// Follows the passages marked once from `start`.
+    fn trace_path(&self, maze: &WithinOrthoMaze) -> Vec<Address> {
+        let mut path = vec![self.start.clone()];
+
+        while path.last() != Some(&self.goal) && path.len() < maze.grid().cell_count() {
+            let next = {
+                let current = path.last().and_then(|addr| addr.from(maze))
+                    .expect("address in maze exists");
+                let before = path.len().checked_sub(2).map(|index| &path[index]);
+
+                current.reachable_neighbours()
+                    .iter()
+                    .filter(|neighbour| current.marks_to(neighbour) == 1)
+                    .map(|neighbour| -> Address { neighbour.into() })
+                    .find(|addr| Some(addr) != before)
+            };
+
+            match next {
+                Some(next) => path.push(next),
+                None => break
+            }
+        }
+
+        path
+    }
+}
+
+
+impl Task<Args> for Tremaux {
+    fn name(&self) -> &'static str {
+        "Tremaux"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+        let mut rng = args.rng.borrow_mut();
+
+        if self.location.is_none() {
+            if let Err(msg) = self.start(&mut *maze) {
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        }
+
+        if self.location.as_ref() == Some(&self.goal) {
+            let path = self.trace_path(&*maze);
+            clear_and_mark_path(&path, &mut *maze);
+
+            let msg = format!("goal reached, path of {} steps", path.len() - 1);
+            self.log_action(&msg);
+            return Status::Done;
+        }
+
+        let (next, msg) = match self.pick_next(&*maze, &mut *rng) {
+            Ok(step) => step,
+            Err(msg) => {
+                clear_and_mark_path(&[], &mut *maze);
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        };
+
+        let location = self.location.clone().expect("walker has a location");
+        location.add_passage_mark(&next, &mut *maze);
+        self.walk_to(&next, &mut *maze);
+
+        self.log_action(msg);
+        Status::Continuing
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Fills dead ends until only the paths from `start` to `goal` and the
+/// loops are left. Built `with_cul_de_sacs`, it also seals the parts hanging
+/// off the rest by a single passage without `start` nor `goal`.
+pub struct DeadEndFilling {
+    start: Address,
+    goal: Address,
+    dead_ends: VecDeque<Address>,
+    seal_cul_de_sacs: bool,
+    cul_de_sacs: Option<VecDeque<Vec<Address>>>,
+    started: bool,
+    filled: usize,
+    action: String
+}
+
+
+/// Cell of the depth first walk looking for cul-de-sacs, with its index in
+/// the walk and the neighbours left to explore.
+struct CulDeSacStep {
+    index: usize,
+    parent: Option<usize>,
+    neighbours: Vec<Address>,
+    next: usize
+}
+
+
+impl DeadEndFilling {
+    pub fn new(start: Address, goal: Address) -> DeadEndFilling {
+        DeadEndFilling {
+            start,
+            goal,
+            dead_ends: VecDeque::new(),
+            seal_cul_de_sacs: false,
+            cul_de_sacs: None,
+            started: false,
+            filled: 0,
+            action: String::new()
+        }
+    }
+
+    pub fn with_cul_de_sacs(start: Address, goal: Address) -> DeadEndFilling {
+        DeadEndFilling {
+            seal_cul_de_sacs: true,
+            .. DeadEndFilling::new(start, goal)
+        }
+    }
+
+    fn log_action(&mut self, current: &Address, msg: &str) {
+        self.action = format!("At {}, {}", current.to_str(), msg);
+    }
+
+    fn start(&mut self, maze: &mut WithinOrthoMaze) -> Result<(), &'static str> {
+        if self.start.from(maze).is_none() || self.goal.from(maze).is_none() {
+            return Err("start or goal outside of the maze");
+        }
+
+        clear_path(maze);
+
+        for addr in maze.grid().crumbs() {
+            if self.is_dead_end(&addr, maze) {
+                addr.mark_active(maze);
+                self.dead_ends.push_back(addr);
+            }
+        }
+
+        self.started = true;
+        Ok(())
+    }
+
+    /// Neighbours of `addr` reachable without crossing filled cells.
+    fn open_neighbours(addr: &Address, maze: &WithinOrthoMaze) -> Vec<Address> {
+        let pos = addr.from(maze).expect("address in maze exists");
+        pos.reachable_neighbours()
+            .into_iter()
+            .filter(|neighbour| !neighbour.is_filled())
+            .map(|neighbour| neighbour.into())
+            .collect()
+    }
+
+    /// Unfilled cells connected to `start`, the goal is among them unless
+    /// the two are in separate parts of the maze.
+    fn unfilled_from_start(&self, maze: &WithinOrthoMaze) -> Vec<Address> {
+        let mut seen : Grid<bool> = Grid::new(maze.grid().columns(), maze.grid().lines());
+        let mut cells = vec![self.start.clone()];
+        if let Some(seen) = seen.at_mut(self.start.column, self.start.line) {
+            *seen = true;
+        }
+
+        let mut index = 0;
+        while index < cells.len() {
+            for neighbour in Self::open_neighbours(&cells[index], maze) {
+                if let Some(seen) = seen.at_mut(neighbour.column, neighbour.line) {
+                    if !*seen {
+                        *seen = true;
+                        cells.push(neighbour);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        cells
+    }
+
+    /// Outermost parts of the unfilled cells entered by a single passage
+    /// and without `goal`, found by a depth first walk from `start`.
+    fn find_cul_de_sacs(&self, maze: &WithinOrthoMaze) -> Vec<Vec<Address>> {
+        let mut indexes : Grid<Option<usize>> =
+            Grid::new(maze.grid().columns(), maze.grid().lines());
+        let mut cells : Vec<Address> = Vec::new();
+        // lowest index reached from the cells walked from each cell, the last
+        // of them and whether the goal is among them
+        let mut lowest : Vec<usize> = Vec::new();
+        let mut last : Vec<usize> = Vec::new();
+        let mut has_goal : Vec<bool> = Vec::new();
+        let mut hanging : Vec<usize> = Vec::new();
+        let mut steps : Vec<CulDeSacStep> = Vec::new();
+
+        let mut next_cell = Some((self.start.clone(), None));
+        loop {
+            if let Some((addr, parent)) = next_cell.take() {
+                let index = cells.len();
+                if let Some(cell_index) = indexes.at_mut(addr.column, addr.line) {
+                    *cell_index = Some(index);
+                }
+                lowest.push(index);
+                last.push(index);
+                has_goal.push(addr == self.goal);
+                steps.push(CulDeSacStep {
+                    index,
+                    parent,
+                    neighbours: Self::open_neighbours(&addr, maze),
+                    next: 0
+                });
+                cells.push(addr);
+            }
+
+            let (index, parent, neighbour) = match steps.last_mut() {
+                Some(step) => {
+                    let neighbour = step.neighbours.get(step.next).cloned();
+                    step.next += 1;
+                    (step.index, step.parent, neighbour)
+                }
+                None => break
+            };
+
+            match neighbour {
+                Some(neighbour) => {
+                    match indexes.at(neighbour.column, neighbour.line).cloned() {
+                        Some(Some(reached)) if Some(reached) != parent => {
+                            lowest[index] = lowest[index].min(reached);
+                        }
+                        Some(None) => next_cell = Some((neighbour, Some(index))),
+                        _ => ()
+                    }
+                }
+                None => {
+                    steps.pop();
+                    last[index] = cells.len() - 1;
+                    if let Some(parent) = parent {
+                        lowest[parent] = lowest[parent].min(lowest[index]);
+                        has_goal[parent] = has_goal[parent] || has_goal[index];
+                        if lowest[index] > parent && !has_goal[index] {
+                            hanging.push(index);
+                        }
+                    }
+                }
+            }
+        }
+
+        // the cells walked from a cell follow it, nested cul-de-sacs are
+        // within the range of the outer one
+        hanging.sort();
+        let mut cul_de_sacs = Vec::new();
+        let mut covered = 0;
+        for index in hanging {
+            if index >= covered {
+                cul_de_sacs.push(cells[index..last[index] + 1].to_vec());
+                covered = last[index] + 1;
+            }
+        }
+        cul_de_sacs
+    }
+
+    /// Seals the next cul-de-sac, `None` once they are all sealed.
+    fn seal_next(&mut self, maze: &mut WithinOrthoMaze) -> Option<Status> {
+        if self.cul_de_sacs.is_none() {
+            let found = self.find_cul_de_sacs(maze);
+            for addr in found.iter().flat_map(|cells| cells.iter()) {
+                addr.mark_active(maze);
+            }
+
+            self.action = format!("no dead end left, {} cul-de-sacs found", found.len());
+            self.cul_de_sacs = Some(found.into_iter().collect());
+            return Some(Status::Continuing);
+        }
+
+        let cells = self.cul_de_sacs.as_mut().and_then(|cul_de_sacs| cul_de_sacs.pop_front())?;
+        for addr in &cells {
+            addr.unmark_active(maze);
+            addr.mark_filled(maze);
+        }
+        self.filled += cells.len();
+
+        let msg = format!("cul-de-sac of {} cells sealed", cells.len());
+        self.log_action(&cells[0], &msg);
+        Some(Status::Continuing)
+    }
+
+    fn is_dead_end(&self, addr: &Address, maze: &WithinOrthoMaze) -> bool {
+        *addr != self.start
+            && *addr != self.goal
+            && !addr.from(maze).map(|pos| pos.is_filled()).unwrap_or(true)
+            // a cell left without any open neighbour is filled as well
+            && Self::open_neighbours(addr, maze).len() <= 1
+    }
+}
+
+
+impl Task<Args> for DeadEndFilling {
+    fn name(&self) -> &'static str {
+        if self.seal_cul_de_sacs { "CulDeSacFilling" } else { "DeadEndFilling" }
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if !self.started {
+            if let Err(msg) = self.start(&mut *maze) {
+                self.action = msg.to_owned();
+                return Status::Aborted(msg.to_owned());
+            }
+        }
+
+        let address = match self.dead_ends.pop_front() {
+            Some(address) => address,
+            None => {
+                if self.seal_cul_de_sacs {
+                    if let Some(status) = self.seal_next(&mut *maze) {
+                        return status;
+                    }
+                }
+
+                let path = self.unfilled_from_start(&*maze);
+                if !path.contains(&self.goal) {
+                    let msg = "goal cannot be reached from start";
+                    clear_and_mark_path(&[], &mut *maze);
+                    self.action = msg.to_owned();
+                    return Status::Aborted(msg.to_owned());
+                }
+
+                clear_and_mark_path(&path, &mut *maze);
+
+                let msg = format!("{} cells filled, {} cells left", self.filled, path.len());
+                self.action = msg;
+                return Status::Done;
+            }
+        };
+
+        address.unmark_active(&mut *maze);
+        if !self.is_dead_end(&address, &*maze) {
+            self.log_action(&address, "not a dead end anymore");
+            return Status::Continuing;
+        }
+
+        address.mark_filled(&mut *maze);
+        self.filled += 1;
+
+        for neighbour in Self::open_neighbours(&address, &*maze) {
+            if self.is_dead_end(&neighbour, &*maze) && !neighbour.is_active(&*maze) {
+                neighbour.mark_active(&mut *maze);
+                self.dead_ends.push_back(neighbour);
+            }
+        }
+
+        self.log_action(&address, "dead end filled");
+        Status::Continuing
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Breadth first searches from `start` and from `goal` until they meet.
+/// Each step explores one cell, but the searches take turns per layer: one
+/// running ahead of the other could first meet it on a longer path.
+pub struct BidirectionalBreadthFirst {
+    ends: [Address; 2],
+    frontiers: [VecDeque<Address>; 2],
+    came_from: [Grid<Option<Address>>; 2],
+    distances: [Grid<usize>; 2],
+    /// Cells of the current layer of each search not explored yet.
+    layer_left: [usize; 2],
+    turn: usize,
+    steps: usize,
+    best: Option<Contact>,
+    location: Option<Address>,
+    action: String
+}
+
+
+/// Passage between the two searches, making a path of `length` steps.
+struct Contact {
+    length: usize,
+    from_start: Address,
+    from_goal: Address
+}
+
+
+impl BidirectionalBreadthFirst {
+    pub fn new(maze: &WithinOrthoMaze, start: Address, goal: Address)
+        -> BidirectionalBreadthFirst
+    {
+        let columns = maze.grid().columns();
+        let lines = maze.grid().lines();
+
+        let mut frontiers = [VecDeque::new(), VecDeque::new()];
+        frontiers[0].push_back(start.clone());
+        frontiers[1].push_back(goal.clone());
+
+        BidirectionalBreadthFirst {
+            ends: [start, goal],
+            frontiers,
+            came_from: [Grid::new(columns, lines), Grid::new(columns, lines)],
+            distances: [Grid::new(columns, lines), Grid::new(columns, lines)],
+            layer_left: [1, 1],
+            turn: 0,
+            steps: 0,
+            best: None,
+            location: None,
+            action: String::new()
+        }
+    }
+
+    fn log_action(&mut self, msg: &str) {
+        self.action = match self.location {
+            Some(ref location) => format!("At {}, {}", location.to_str(), msg),
+            None => msg.to_owned()
+        };
+    }
+
+    fn start(&mut self, maze: &mut WithinOrthoMaze) -> Result<(), &'static str> {
+        if self.ends.iter().any(|end| end.from(maze).is_none()) {
+            return Err("start or goal outside of the maze");
+        }
+
+        clear_path(maze);
+        Self::clear_frontiers(maze);
+        for (side, end) in self.ends.iter().enumerate() {
+            end.mark_frontier(side, maze);
+        }
+        Ok(())
+    }
+
+    fn clear_frontiers(maze: &mut WithinOrthoMaze) {
+        for addr in maze.grid().crumbs() {
+            addr.unmark_frontier(maze);
+        }
+    }
+
+    fn walk_to(&mut self, addr: &Address, maze: &mut WithinOrthoMaze) {
+        if let Some(ref location) = self.location {
+            location.unmark_current(maze);
+        }
+        addr.mark_current(maze);
+        self.location = Some(addr.clone());
+    }
+
+    /// Joins the half from `start` to `from_start` with the half from
+    /// `from_goal` to `goal`.
+    fn join_path(&self, from_start: &Address, from_goal: &Address) -> Vec<Address> {
+        let mut path = trace_path(&self.came_from[0], from_start);
+        let mut second_half = trace_path(&self.came_from[1], from_goal);
+        second_half.reverse();
+
+        path.extend(second_half);
+        path
+    }
+
+    fn distance(&self, side: usize, addr: &Address) -> usize {
+        self.distances[side].at(addr.column, addr.line).cloned().unwrap_or(0)
+    }
+
+    /// Keeps the passage from `addr`, explored by the search of `side`, to
+    /// `neighbour` if it makes the shortest path so far.
+    fn touch(&mut self, side: usize, addr: &Address, neighbour: &Address) {
+        let length = self.distance(side, addr) + 1 + self.distance(1 - side, neighbour);
+        if self.best.as_ref().map(|best| length < best.length).unwrap_or(true) {
+            let (from_start, from_goal) = if side == 0 {
+                (addr.clone(), neighbour.clone())
+            } else {
+                (neighbour.clone(), addr.clone())
+            };
+            self.best = Some(Contact { length, from_start, from_goal });
+        }
+    }
+
+    fn meet(&mut self, path: Vec<Address>, meeting: &Address, maze: &mut WithinOrthoMaze)
+        -> Status
+    {
+        Self::clear_frontiers(maze);
+        clear_and_mark_path(&path, maze);
+        meeting.mark_meeting(maze);
+        self.location = Some(meeting.clone());
+
+        let msg = format!("searches met after {} steps, path of {} steps",
+                          self.steps, path.len() - 1);
+        self.log_action(&msg);
+        Status::Done
+    }
+}
+
+
+impl Task<Args> for BidirectionalBreadthFirst {
+    fn name(&self) -> &'static str {
+        "BidirectionalBreadthFirst"
+    }
+
+    fn action<'t>(&'t self) -> Option<Cow<'t, str>> {
+        Some(Cow::Borrowed(&self.action))
+    }
+
+    fn execute_one(&mut self, args: &mut Args) -> Status {
+        let mut maze = args.maze.borrow_mut();
+
+        if self.location.is_none() {
+            if let Err(msg) = self.start(&mut *maze) {
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+
+            if self.ends[0] == self.ends[1] {
+                let start = self.ends[0].clone();
+                return self.meet(vec![start.clone()], &start, &mut *maze);
+            }
+        }
+
+        let side = self.turn;
+        let other = 1 - side;
+        self.steps += 1;
+
+        let address = match self.frontiers[side].pop_front() {
+            Some(address) => address,
+            None => {
+                let msg = "goal cannot be reached from start";
+                Self::clear_frontiers(&mut *maze);
+                clear_and_mark_path(&[], &mut *maze);
+                self.log_action(msg);
+                return Status::Aborted(msg.to_owned());
+            }
+        };
+
+        self.walk_to(&address, &mut *maze);
+        self.layer_left[side] -= 1;
+        let distance = self.distance(side, &address) + 1;
+
+        let neighbours : Vec<(Address, Option<usize>)> = {
+            let pos = address.from(&*maze).expect("address in maze exists");
+            pos.reachable_neighbours()
+                .iter()
+                .map(|neighbour| (neighbour.into(), neighbour.frontier()))
+                .collect()
+        };
+
+        for (neighbour, frontier) in neighbours {
+            if frontier == Some(other) {
+                self.touch(side, &address, &neighbour);
+            } else if frontier.is_none() {
+                neighbour.mark_frontier(side, &mut *maze);
+                if let Some(previous) = self.came_from[side].at_mut(neighbour.column, neighbour.line) {
+                    *previous = Some(address.clone());
+                }
+                if let Some(cell) = self.distances[side].at_mut(neighbour.column, neighbour.line) {
+                    *cell = distance;
+                }
+                self.frontiers[side].push_back(neighbour);
+            }
+        }
+
+        if self.layer_left[side] == 0 {
+            if let Some(contact) = self.best.take() {
+                let path = self.join_path(&contact.from_start, &contact.from_goal);
+                return self.meet(path, &contact.from_goal, &mut *maze);
+            }
+
+            self.layer_left[side] = self.frontiers[side].len();
+            self.turn = other;
+        }
+
+        let msg = if self.best.is_some() {
+            "searches touch, finishing the layer".to_owned()
+        } else {
+            format!("{} and {} cells in sight",
+                    self.frontiers[0].len(), self.frontiers[1].len())
+        };
+        self.log_action(&msg);
+        Status::Continuing
+    }
+}
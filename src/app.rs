@@ -1,3 +1,4 @@
+extern crate rand;
 extern crate piston;
 extern crate graphics;
 extern crate opengl_graphics;
@@ -10,48 +11,22 @@ use piston::input::{keyboard, RenderArgs, UpdateArgs, Button, Key};
 use opengl_graphics::{GlGraphics};
 
 use super::settings::{DEBUG_GATE, DEBUG_ALGO};
-use super::maze::{OrthoMaze, WithinOrthoMaze};
+use super::maze::OrthoMaze;
 use super::maze_render::{MazeRenderer, StaticMazeRenderer};
 use super::highmap::OrthoHighMap;
+use super::text_render::{Charset, TextMazeRenderer};
+use super::archive;
 use super::algo;
+use super::algo::base::{MazeRng, Seed, seeded_rng};
+use super::algo::catalog::{Algo, Solver, Tuning};
+use super::algo::seeding::{DijkstraWalk, SpawnLayout};
 use super::task;
 
 
-#[derive(Clone)]
-enum Algo {
-    BinaryTree,
-    SideWinder,
-    AldousBroder,
-    Wilson
-}
-
-
-impl Algo {
-    fn name(&self) -> &'static str {
-        match *self {
-            Algo::BinaryTree => "BinaryTree",
-            Algo::SideWinder => "SideWinder",
-            Algo::AldousBroder => "AldousBroder",
-            Algo::Wilson => "Wilson"
-        }
-    }
-
-    fn create(&self, maze: &WithinOrthoMaze)
-        -> Box<task::Task<algo::base::Args>> {
-            match *self {
-                Algo::BinaryTree => Box::new(algo::carving::BinaryTree::new(maze)),
-                Algo::SideWinder => Box::new(algo::carving::SideWinder::new(maze)),
-                Algo::AldousBroder => Box::new(algo::carving::AldousBroder::new(maze)),
-                Algo::Wilson => Box::new(algo::carving::Wilson::new(maze))
-            }
-        }
-}
-
-
-// ----------------------------------------------------------------------------
+type Second = f64;
 
 
-type Second = f64;
+const SAVE_PATH : &str = "mazing.maze";
 
 
 enum Speed {
@@ -118,8 +93,15 @@ pub struct App {
     mr: StaticMazeRenderer,
     last_carve_algo: Option<Algo>,
     next_carve_algo: Option<Algo>,
+    size: (usize, usize),
     maze: Rc<RefCell<OrthoMaze>>,
     highmap: Rc<RefCell<OrthoHighMap>>,
+    seed: Seed,
+    seed_entry: Option<String>,
+    rng: Rc<RefCell<MazeRng>>,
+    tuning: Tuning,
+    solver: Solver,
+    spawns: SpawnLayout,
     exec: Execution
 }
 
@@ -131,29 +113,66 @@ impl App {
 
         let maze = Rc::new(RefCell::new(OrthoMaze::new(w, h)));
         let highmap = Rc::new(RefCell::new(OrthoHighMap::new(w, h)));
+        let seed = rand::random();
         App {
             gl,
             mr: StaticMazeRenderer::new(),
+            size: (w, h),
             maze,
             highmap,
             last_carve_algo: None,
             next_carve_algo: None,
+            seed,
+            seed_entry: None,
+            rng: Rc::new(RefCell::new(seeded_rng(seed))),
+            tuning: Tuning::new(),
+            solver: Solver::BreadthFirst,
+            spawns: SpawnLayout::Center,
             exec: Execution::new(speed)
         }
     }
 
+    fn maze_size(&self) -> (usize, usize) {
+        use grid::Within;
+
+        let maze = self.maze.borrow();
+        (maze.grid().columns(), maze.grid().lines())
+    }
+
+    fn is_carving_endless(&self) -> bool {
+        let endless = self.last_carve_algo.as_ref().is_some_and(Algo::is_endless);
+        endless && !self.exec.tasks.is_empty()
+    }
+
+    pub fn title(&self) -> String {
+        if let Some(ref entry) = self.seed_entry {
+            format!("mazing - seed: {}_", entry)
+        } else {
+            let algo = self.last_carve_algo.as_ref()
+                .map(|type_| type_.name())
+                .unwrap_or("no algo");
+            format!("mazing - {} - seed {}", algo, self.seed)
+        }
+    }
+
     fn reset_algo(&mut self, type_: Algo) {
-        println!("[app] Reset algo with {}", type_.name());
+        println!("[app] Reset algo with {}, seed {}", type_.name(), self.seed);
 
         self.exec.reset();
+        self.rng = Rc::new(RefCell::new(seeded_rng(self.seed)));
+
+        let (w, h) = self.size;
+        self.maze = Rc::new(RefCell::new(type_.blank_maze(w, h)));
+        self.highmap = Rc::new(RefCell::new(OrthoHighMap::new(w, h)));
 
         let maze = self.maze.borrow();
-        let algo = type_.create(&*maze);
+        let algo = type_.create(&*maze, &self.tuning);
 
         self.last_carve_algo = Some(type_);
         self.exec.tasks.stack(algo);
 
-        let depth_walker = algo::seeding::DijkstraWalk::new(&*maze);
+        let sources = self.spawns.sources(w, h);
+        let depth_walker = DijkstraWalk::from_sources(sources);
         self.exec.tasks.stack(Box::new(depth_walker));
     }
 
@@ -166,25 +185,74 @@ impl App {
         self.next_carve_algo = Some(type_);
     }
 
-    fn reset_maze(&mut self) {
-        println!("[app] Reset maze");
-        self.exec.reset();
+    fn stack_solver(&mut self) {
+        use grid::Address;
+
+        let (w, h) = self.maze_size();
+        if w == 0 || h == 0 {
+            return;
+        }
+
+        let start = Address { column: 0, line: 0 };
+        let goal = Address { column: w - 1, line: h - 1 };
+        println!("[app] Solve from {} to {} with {}",
+                 start.to_str(), goal.to_str(), self.solver.description());
+
+        let maze = self.maze.borrow();
+        self.exec.tasks.stack(self.solver.create(&*maze, start, goal));
+    }
+
+    fn print_snapshot(&self) {
+        let mut renderer = TextMazeRenderer::new();
+        renderer.set_charset(Charset::Unicode);
+        renderer.toggle_highmap();
+        renderer.toggle_status();
 
-        let (w, h);
-        {
-            use grid::Within;
+        println!("[app] Snapshot, seed {}", self.seed);
+        print!("{}", renderer.render(&self.maze.borrow(), &self.highmap.borrow()));
+    }
+
+    fn save_maze(&self) {
+        let maze = self.maze.borrow();
+        let highmap = self.highmap.borrow();
 
-            let maze = self.maze.borrow();
-            w = maze.grid().columns();
-            h = maze.grid().lines();
+        match archive::save_file(SAVE_PATH, &maze, Some(&*highmap)) {
+            Ok(()) => println!("[app] Saved maze to {}", SAVE_PATH),
+            Err(err) => println!("[app] Failed to save maze to {}: {}", SAVE_PATH, err)
         }
+    }
 
-        self.maze = Rc::new(RefCell::new(OrthoMaze::new(w, h)));
-        self.highmap = Rc::new(RefCell::new(OrthoHighMap::new(w, h)));
+    fn load_maze(&mut self) {
+        let (maze, highmap) = match archive::load_file(SAVE_PATH) {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                println!("[app] Failed to load maze from {}: {}", SAVE_PATH, err);
+                return;
+            }
+        };
+
+        println!("[app] Loaded maze from {}", SAVE_PATH);
+        self.exec.reset();
+
+        use grid::Within;
+        self.size = (maze.grid().columns(), maze.grid().lines());
+        let highmap = highmap.unwrap_or_else(|| OrthoHighMap::new(self.size.0, self.size.1));
+        self.maze = Rc::new(RefCell::new(maze));
+        self.highmap = Rc::new(RefCell::new(highmap));
+        self.last_carve_algo = None;
+    }
+
+    fn reset_maze(&mut self) {
+        println!("[app] Reset maze");
+        self.exec.reset();
 
         let algo = self.next_carve_algo.clone().or(self.last_carve_algo.clone());
         if let Some(type_) = algo {
             self.reset_algo(type_);
+        } else {
+            let (w, h) = self.size;
+            self.maze = Rc::new(RefCell::new(OrthoMaze::new(w, h)));
+            self.highmap = Rc::new(RefCell::new(OrthoHighMap::new(w, h)));
         }
 
         self.next_carve_algo = self.last_carve_algo.clone();
@@ -232,32 +300,90 @@ impl App {
     fn commit_one_step(&mut self) {
         let maze = self.maze.clone();
         let highmap = self.highmap.clone();
-        let args = algo::base::Args { maze, highmap };
+        let rng = self.rng.clone();
+        let args = algo::base::Args { maze, highmap, rng };
         self.exec.tasks.run_step(args);
     }
 
     fn commit_one_task(&mut self) {
         let maze = self.maze.clone();
         let highmap = self.highmap.clone();
-        let args = algo::base::Args { maze, highmap };
+        let rng = self.rng.clone();
+        let args = algo::base::Args { maze, highmap, rng };
         self.exec.tasks.run_task(args);
     }
 
     fn commit_all(&mut self) {
         let maze = self.maze.clone();
         let highmap = self.highmap.clone();
-        let args = algo::base::Args { maze, highmap };
+        let rng = self.rng.clone();
+        let args = algo::base::Args { maze, highmap, rng };
         self.exec.tasks.run(args);
     }
 
+    fn start_seed_entry(&mut self) {
+        println!("[app] Type a seed, Return to apply, S to cancel");
+        self.seed_entry = Some(String::new());
+    }
+
+    fn edit_seed(&mut self, key: Key) {
+        let digit = match key {
+            Key::D0 | Key::NumPad0 => Some('0'),
+            Key::D1 | Key::NumPad1 => Some('1'),
+            Key::D2 | Key::NumPad2 => Some('2'),
+            Key::D3 | Key::NumPad3 => Some('3'),
+            Key::D4 | Key::NumPad4 => Some('4'),
+            Key::D5 | Key::NumPad5 => Some('5'),
+            Key::D6 | Key::NumPad6 => Some('6'),
+            Key::D7 | Key::NumPad7 => Some('7'),
+            Key::D8 | Key::NumPad8 => Some('8'),
+            Key::D9 | Key::NumPad9 => Some('9'),
+            _ => None
+        };
+
+        match key {
+            Key::Return | Key::NumPadEnter => {
+                let entry = self.seed_entry.take().unwrap_or_default();
+                match entry.parse::<Seed>() {
+                    Ok(seed) => {
+                        println!("[app] Seed is {}", seed);
+                        self.seed = seed;
+                        self.reset_maze();
+                    }
+                    Err(_) if entry.is_empty() => println!("[app] Seed unchanged"),
+                    Err(_) => println!("[app] Invalid seed {}", entry)
+                }
+            },
+            Key::Backspace => {
+                self.seed_entry.as_mut().map(|entry| entry.pop());
+            },
+            Key::S => {
+                println!("[app] Seed unchanged");
+                self.seed_entry = None;
+            },
+            _ => {
+                if let (Some(entry), Some(digit)) = (self.seed_entry.as_mut(), digit) {
+                    entry.push(digit);
+                }
+            }
+        }
+    }
+
     pub fn button_pressed(
         &mut self,
         args: &Button,
         modkeys: &keyboard::ModifierKey)
     {
+        if self.seed_entry.is_some() {
+            if let Button::Keyboard(key) = *args {
+                self.edit_seed(key);
+            }
+            return;
+        }
+
         let has_ctrl = modkeys.contains(keyboard::ModifierKey::CTRL);
         match *args {
-            Button::Keyboard(key) if key == Key::Space && !has_ctrl => {
+            Button::Keyboard(Key::Space) if !has_ctrl => {
                 self.exec.active = !self.exec.active;
 
                 if self.exec.active {
@@ -268,50 +394,128 @@ impl App {
                     self.exec.waited_time = 0.0;
                 }
             },
-            Button::Keyboard(key) if key == Key::Space && has_ctrl => {
+            Button::Keyboard(Key::Space) if has_ctrl => {
                 self.commit_one_step();
             },
-            Button::Keyboard(key) if key == Key::Return && has_ctrl => {
+            Button::Keyboard(Key::Return) if self.is_carving_endless() => {
+                println!("[app] Endless algo never completes, run it with Space");
+            },
+            Button::Keyboard(Key::Return) if has_ctrl => {
                 self.commit_one_task();
             },
-            Button::Keyboard(key) if key == Key::Return && !has_ctrl => {
+            Button::Keyboard(Key::Return) if !has_ctrl => {
                 self.commit_all();
             },
-            Button::Keyboard(key) if key == Key::D1 => {
+            Button::Keyboard(Key::D1) => {
                 self.select_algo(Algo::BinaryTree);
             },
-            Button::Keyboard(key) if key == Key::D2 => {
+            Button::Keyboard(Key::D2) => {
                 self.select_algo(Algo::SideWinder);
             },
-            Button::Keyboard(key) if key == Key::D3 => {
+            Button::Keyboard(Key::D3) => {
                 self.select_algo(Algo::AldousBroder);
             },
-            Button::Keyboard(key) if key == Key::D4 => {
+            Button::Keyboard(Key::D4) => {
                 self.select_algo(Algo::Wilson);
             },
-            Button::Keyboard(key) if key == Key::G => {
+            Button::Keyboard(Key::D5) => {
+                self.select_algo(Algo::RecursiveBacktracker);
+            },
+            Button::Keyboard(Key::D6) => {
+                self.select_algo(Algo::HuntAndKill);
+            },
+            Button::Keyboard(Key::D7) => {
+                let selection = algo::carving::Selection::default();
+                self.select_algo(Algo::GrowingTree(selection));
+            },
+            Button::Keyboard(Key::D8) => {
+                self.select_algo(Algo::Kruskal);
+            },
+            Button::Keyboard(Key::D9) => {
+                self.select_algo(Algo::SimplifiedPrim);
+            },
+            Button::Keyboard(Key::D0) => {
+                self.select_algo(Algo::TruePrim);
+            },
+            Button::Keyboard(Key::E) if !has_ctrl => {
+                self.select_algo(Algo::Eller);
+            },
+            Button::Keyboard(Key::E) if has_ctrl => {
+                self.select_algo(Algo::EllerEndless);
+            },
+            Button::Keyboard(Key::R) if !has_ctrl => {
+                self.select_algo(Algo::RecursiveDivision(1));
+            },
+            Button::Keyboard(Key::R) if has_ctrl => {
+                self.select_algo(Algo::RecursiveDivision(4));
+            },
+            Button::Keyboard(Key::Left) => {
+                self.tuning.change_bias(-0.05);
+                println!("[app] Bias is {} (applied on next reset)", self.tuning.bias);
+            },
+            Button::Keyboard(Key::Right) => {
+                self.tuning.change_bias(0.05);
+                println!("[app] Bias is {} (applied on next reset)", self.tuning.bias);
+            },
+            Button::Keyboard(Key::C) => {
+                self.tuning.next_corner();
+                println!("[app] Corner is {} (applied on next reset)",
+                         self.tuning.corner.name());
+            },
+            Button::Keyboard(Key::G) => {
                 self.mr.toggle_gate();
             },
-            Button::Keyboard(key) if key == Key::H => {
+            Button::Keyboard(Key::H) => {
                 self.mr.toggle_highmap();
             },
-            Button::Keyboard(key) if key == Key::Backspace => {
+            Button::Keyboard(Key::V) => {
+                self.mr.toggle_voronoi();
+            },
+            Button::Keyboard(Key::P) => {
+                self.spawns = self.spawns.next();
+                println!("[app] Spawn layout is {} (applied on next reset)",
+                         self.spawns.name());
+            },
+            Button::Keyboard(Key::Backspace) if !has_ctrl => {
+                self.seed = rand::random();
                 self.reset_maze();
             },
-            Button::Keyboard(key) if key == Key::PageUp => {
+            Button::Keyboard(Key::Backspace) if has_ctrl => {
+                self.reset_maze();
+            },
+            Button::Keyboard(Key::S) if !has_ctrl => {
+                self.start_seed_entry();
+            },
+            Button::Keyboard(Key::S) if has_ctrl => {
+                self.save_maze();
+            },
+            Button::Keyboard(Key::O) if has_ctrl => {
+                self.load_maze();
+            },
+            Button::Keyboard(Key::F) if !has_ctrl => {
+                self.stack_solver();
+            },
+            Button::Keyboard(Key::F) if has_ctrl => {
+                self.solver = self.solver.next();
+                println!("[app] Solver is {}", self.solver.description());
+            },
+            Button::Keyboard(Key::T) => {
+                self.print_snapshot();
+            },
+            Button::Keyboard(Key::PageUp) => {
                 self.exec.change_speed(Speed::VerySlow);
             },
-            Button::Keyboard(key) if key == Key::PageDown => {
+            Button::Keyboard(Key::PageDown) => {
                 self.exec.change_speed(Speed::Normal);
             },
-            Button::Keyboard(key) if key == Key::Up => {
+            Button::Keyboard(Key::Up) => {
                 if modkeys.contains(keyboard::ModifierKey::CTRL) {
                     let d = self.mr.distance_per_color();
                     let d = d + std::cmp::max(1, d / 10);
                     self.mr.set_distance_per_color(std::cmp::min(d, 200));
                 }
             },
-            Button::Keyboard(key) if key == Key::Down => {
+            Button::Keyboard(Key::Down) => {
                 if modkeys.contains(keyboard::ModifierKey::CTRL) {
                     let d = self.mr.distance_per_color();
                     let d = d - std::cmp::max(1, d / 10);
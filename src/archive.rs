@@ -0,0 +1,384 @@
+//! Binary archive of a maze and its highmap.
+//!
+//! All numbers are little endian:
+//!
+//! | field    | size            | content                                  |
+//! |----------|-----------------|------------------------------------------|
+//! | magic    | 6               | `MAZING`                                 |
+//! | version  | 2               | `FORMAT_VERSION`                         |
+//! | columns  | 4               |                                          |
+//! | lines    | 4               |                                          |
+//! | flags    | 1               | bit 0 set when heights follow the gates  |
+//! | gates    | 1 per cell      | bit 0 open on the right, bit 1 open down |
+//! | heights  | 4 per cell      | `NO_HEIGHT` for cells without height     |
+//! | checksum | 4               | FNV-1a of all the previous bytes         |
+//!
+//! Cells are stored line after line.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use super::grid::Within;
+use super::maze::OrthoMaze;
+use super::highmap::OrthoHighMap;
+
+
+const MAGIC : &[u8; 6] = b"MAZING";
+
+pub const FORMAT_VERSION : u16 = 1;
+
+const FLAG_HEIGHTS : u8 = 0b01;
+
+const GATE_RIGHT : u8 = 0b01;
+const GATE_DOWN : u8 = 0b10;
+
+const NO_HEIGHT : u32 = u32::MAX;
+
+/// Refuse to allocate more cells than this, a corrupt size would otherwise
+/// exhaust the memory before the checksum is read.
+const MAX_CELLS : usize = 1 << 26;
+
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    NotAMaze,
+    UnsupportedVersion(u16),
+    Truncated,
+    TooLarge(usize, usize),
+    UnknownFlags(u8),
+    InvalidGate(usize, usize),
+    ChecksumMismatch,
+    TrailingData
+}
+
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::Io(ref err) => write!(f, "{}", err),
+            LoadError::NotAMaze => write!(f, "not a maze archive"),
+            LoadError::UnsupportedVersion(version) =>
+                write!(f, "unsupported format version {}, expected {}",
+                       version, FORMAT_VERSION),
+            LoadError::Truncated => write!(f, "archive is truncated"),
+            LoadError::TooLarge(columns, lines) =>
+                write!(f, "maze of {}x{} cells is too large", columns, lines),
+            LoadError::UnknownFlags(flags) => write!(f, "unknown flags {:#04x}", flags),
+            LoadError::InvalidGate(column, line) =>
+                write!(f, "gate of {}:{} opens outside of the maze", column, line),
+            LoadError::ChecksumMismatch => write!(f, "checksum mismatch, archive is corrupt"),
+            LoadError::TrailingData => write!(f, "unexpected data after the checksum")
+        }
+    }
+}
+
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> LoadError {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            LoadError::Truncated
+        } else {
+            LoadError::Io(err)
+        }
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// FNV-1a hash of everything written or read through it.
+struct Checksum<T> {
+    inner: T,
+    hash: u32
+}
+
+
+impl<T> Checksum<T> {
+    fn new(inner: T) -> Checksum<T> {
+        Checksum { inner, hash: 0x811c_9dc5 }
+    }
+
+    fn feed(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u32;
+            self.hash = self.hash.wrapping_mul(0x0100_0193);
+        }
+    }
+}
+
+
+impl<W: Write> Checksum<W> {
+    fn put(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.feed(bytes);
+        self.inner.write_all(bytes)
+    }
+}
+
+
+impl<R: Read> Checksum<R> {
+    fn take(&mut self, bytes: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(bytes)?;
+        self.feed(bytes);
+        Ok(())
+    }
+
+    fn take_u32(&mut self) -> io::Result<u32> {
+        let mut bytes = [0; 4];
+        self.take(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+pub fn save<W: Write>(w: W, maze: &OrthoMaze, highmap: Option<&OrthoHighMap>)
+    -> io::Result<()>
+{
+    let columns = maze.grid().columns();
+    let lines = maze.grid().lines();
+    let mut out = Checksum::new(w);
+
+    out.put(MAGIC)?;
+    out.put(&FORMAT_VERSION.to_le_bytes())?;
+    out.put(&(columns as u32).to_le_bytes())?;
+    out.put(&(lines as u32).to_le_bytes())?;
+    out.put(&[if highmap.is_some() { FLAG_HEIGHTS } else { 0 }])?;
+
+    let mut gates = Vec::with_capacity(columns * lines);
+    for pos in maze.grid().iter() {
+        let mut gate = 0;
+        if pos.can_move_right() {
+            gate |= GATE_RIGHT;
+        }
+        if pos.can_move_down() {
+            gate |= GATE_DOWN;
+        }
+        gates.push(gate);
+    }
+    out.put(&gates)?;
+
+    if let Some(highmap) = highmap {
+        for address in maze.grid().crumbs() {
+            let height = address.from(highmap)
+                .and_then(|pos| pos.height())
+                .map(|height| height as u32)
+                .unwrap_or(NO_HEIGHT);
+            out.put(&height.to_le_bytes())?;
+        }
+    }
+
+    let hash = out.hash;
+    out.inner.write_all(&hash.to_le_bytes())?;
+    out.inner.flush()
+}
+
+
+pub fn load<R: Read>(r: R) -> Result<(OrthoMaze, Option<OrthoHighMap>), LoadError> {
+    let mut input = Checksum::new(r);
+
+    let mut magic = [0; 6];
+    input.take(&mut magic).map_err(|_| LoadError::NotAMaze)?;
+    if &magic != MAGIC {
+        return Err(LoadError::NotAMaze);
+    }
+
+    let mut version = [0; 2];
+    input.take(&mut version)?;
+    let version = u16::from_le_bytes(version);
+    if version != FORMAT_VERSION {
+        return Err(LoadError::UnsupportedVersion(version));
+    }
+
+    let columns = input.take_u32()? as usize;
+    let lines = input.take_u32()? as usize;
+    if columns.checked_mul(lines).map(|cells| cells > MAX_CELLS).unwrap_or(true) {
+        return Err(LoadError::TooLarge(columns, lines));
+    }
+
+    let mut flags = [0; 1];
+    input.take(&mut flags)?;
+    let flags = flags[0];
+    if flags & !FLAG_HEIGHTS != 0 {
+        return Err(LoadError::UnknownFlags(flags));
+    }
+
+    let mut gates = vec![0; columns * lines];
+    input.take(&mut gates)?;
+
+    let mut maze = OrthoMaze::new(columns, lines);
+    for (index, gate) in gates.iter().enumerate() {
+        let (column, line) = (index % columns, index / columns);
+
+        let right = gate & GATE_RIGHT != 0;
+        let down = gate & GATE_DOWN != 0;
+        if gate & !(GATE_RIGHT | GATE_DOWN) != 0
+            || (right && column + 1 == columns)
+            || (down && line + 1 == lines) {
+            return Err(LoadError::InvalidGate(column, line));
+        }
+
+        if right {
+            maze.carve(column, line, column + 1, line);
+        }
+        if down {
+            maze.carve(column, line, column, line + 1);
+        }
+    }
+
+    let highmap = if flags & FLAG_HEIGHTS != 0 {
+        let mut highmap = OrthoHighMap::new(columns, lines);
+        for index in 0..columns * lines {
+            let (column, line) = (index % columns, index / columns);
+
+            let height = input.take_u32()?;
+            if height == NO_HEIGHT {
+                continue;
+            }
+
+            let height = height as usize;
+            if let Some(mut pos) = highmap.grid_mut().cell_mut(column, line) {
+                pos.set_depth(height);
+            }
+            highmap.highest = highmap.highest.max(height);
+        }
+        Some(highmap)
+    } else {
+        None
+    };
+
+    let expected = input.hash;
+    let mut hash = [0; 4];
+    input.inner.read_exact(&mut hash)?;
+    if u32::from_le_bytes(hash) != expected {
+        return Err(LoadError::ChecksumMismatch);
+    }
+
+    let mut extra = [0; 1];
+    if input.inner.read(&mut extra)? != 0 {
+        return Err(LoadError::TrailingData);
+    }
+
+    Ok((maze, highmap))
+}
+
+
+pub fn save_file<P: AsRef<Path>>(path: P, maze: &OrthoMaze, highmap: Option<&OrthoHighMap>)
+    -> io::Result<()>
+{
+    let file = File::create(path)?;
+    save(io::BufWriter::new(file), maze, highmap)
+}
+
+
+pub fn load_file<P: AsRef<Path>>(path: P)
+    -> Result<(OrthoMaze, Option<OrthoHighMap>), LoadError>
+{
+    let file = File::open(path).map_err(LoadError::Io)?;
+    load(io::BufReader::new(file))
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_maze() -> OrthoMaze {
+        let mut maze = OrthoMaze::new(4, 3);
+        for column in 0..3 {
+            maze.carve(column, 0, column + 1, 0);
+        }
+        for column in 0..4 {
+            maze.carve(column, 0, column, 1);
+        }
+        maze.carve(1, 1, 1, 2);
+        maze.carve(0, 2, 1, 2);
+        maze.carve(2, 2, 3, 2);
+        maze.carve(3, 1, 3, 2);
+        maze
+    }
+
+    fn sample_highmap() -> OrthoHighMap {
+        let mut highmap = OrthoHighMap::new(4, 3);
+        for index in 0..11 {
+            if let Some(mut pos) = highmap.grid_mut().cell_mut(index % 4, index / 4) {
+                pos.set_depth(index * 3);
+            }
+        }
+        highmap.highest = 30;
+        highmap
+    }
+
+    fn gates(maze: &OrthoMaze) -> Vec<(bool, bool)> {
+        maze.grid().iter()
+            .map(|pos| (pos.can_move_right(), pos.can_move_down()))
+            .collect()
+    }
+
+    fn heights(highmap: &OrthoHighMap) -> Vec<Option<usize>> {
+        highmap.grid().iter().map(|pos| pos.height()).collect()
+    }
+
+    fn saved(maze: &OrthoMaze, highmap: Option<&OrthoHighMap>) -> Vec<u8> {
+        let mut data = Vec::new();
+        save(&mut data, maze, highmap).expect("saving a maze in memory");
+        data
+    }
+
+    #[test]
+    fn loads_a_saved_maze_back() {
+        let maze = sample_maze();
+        let (loaded, highmap) = load(&saved(&maze, None)[..]).expect("saved maze loads");
+
+        assert_eq!(loaded.grid().columns(), 4);
+        assert_eq!(loaded.grid().lines(), 3);
+        assert_eq!(gates(&loaded), gates(&maze));
+        assert!(highmap.is_none());
+    }
+
+    #[test]
+    fn loads_the_saved_heights_back() {
+        let maze = sample_maze();
+        let highmap = sample_highmap();
+        let data = saved(&maze, Some(&highmap));
+
+        let (loaded, loaded_highmap) = load(&data[..]).expect("saved maze loads");
+        let loaded_highmap = loaded_highmap.expect("heights are saved");
+
+        assert_eq!(gates(&loaded), gates(&maze));
+        assert_eq!(heights(&loaded_highmap), heights(&highmap));
+        assert_eq!(loaded_highmap.highest, 30);
+    }
+
+    #[test]
+    fn refuses_a_corrupt_archive() {
+        let mut data = saved(&sample_maze(), None);
+        let gates_offset = MAGIC.len() + 2 + 4 + 4 + 1;
+        data[gates_offset] ^= GATE_DOWN;
+
+        match load(&data[..]) {
+            Err(LoadError::ChecksumMismatch) => (),
+            Err(err) => panic!("unexpected error: {}", err),
+            Ok(_) => panic!("corrupt archive loaded")
+        }
+    }
+
+    #[test]
+    fn refuses_a_truncated_archive() {
+        let data = saved(&sample_maze(), None);
+
+        match load(&data[..data.len() - 1]) {
+            Err(LoadError::Truncated) => (),
+            Err(err) => panic!("unexpected error: {}", err),
+            Ok(_) => panic!("truncated archive loaded")
+        }
+    }
+}
@@ -0,0 +1,599 @@
+extern crate rand;
+extern crate mazing;
+
+
+use std::env;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+
+use mazing::task;
+use mazing::algo::base::{Args, Seed, seeded_rng};
+use mazing::algo::catalog::{Algo, Solver, Tuning};
+use mazing::algo::solving::{Hand, Heuristic};
+use mazing::algo::seeding::DijkstraWalk;
+use mazing::algo::carving::Corner;
+use mazing::grid::{Address, Within};
+use mazing::maze::OrthoMaze;
+use mazing::highmap::OrthoHighMap;
+use mazing::text_parse;
+use mazing::archive;
+use mazing::code::{self, Encoding};
+use mazing::text_render::{Charset, TextMazeRenderer};
+use mazing::palette::{self, CellFill, Rgba};
+use mazing::svg_render::SvgMazeRenderer;
+#[cfg(feature = "raster")]
+use mazing::raster::RasterMazeRenderer;
+#[cfg(feature = "raster")]
+use mazing::record::GifRecorder;
+
+
+const USAGE : &str = "\
+Usage: mazing-cli [options]
+
+Options:
+    -a, --algo NAME      generation algorithm (default: Wilson)
+    -s, --size WxH       maze size in cells (default: 20x10)
+    -r, --seed N         seed of the random generator (default: random)
+    -i, --input FILE     read an ASCII maze or a .maze archive from FILE
+                         instead of generating one
+        --code CODE      rebuild the maze of a base64 maze code
+        --hex-code CODE  rebuild the maze of an hexadecimal maze code
+    -o, --output FILE    write the maze to FILE instead of stdout
+        --bias P         bias of BinaryTree and SideWinder (default: 0.5)
+        --corner C       corner of BinaryTree and SideWinder: NE, NW, SE, SW
+    -f, --format FMT     output format: text, svg, png, gif, maze, code, hex
+                         (default: from the output file extension, text
+                         otherwise)
+    -c, --charset SET    characters of the walls: ascii, unicode (default: ascii)
+        --depth          show the distance to the start of every cell
+        --sources LIST   cells the distances are measured from, like 0:0,9:9,
+                         implies --depth (default: the centre)
+        --voronoi        colour the cells by their closest source, implies --depth
+        --status         mark the current (@) and active (*) cells in text
+        --solve          mark the shortest path from the top left to the bottom right
+        --solver NAME    algorithm finding the path, implies --solve (default: BreadthFirst)
+        --heuristic H    estimate of AStar: manhattan, euclidean, zero (default: manhattan)
+        --hand H         hand of WallFollower on the wall: left, right (default: left)
+        --from C:L       column and line where the path starts (default: 0:0)
+        --to C:L         column and line where the path ends (default: bottom right)
+        --cell-size N    size of a cell in svg and png (default: 10)
+        --line-thickness N
+                         thickness of the walls in svg and png (default: 1)
+        --wall-color HEX colour of the walls in images (default: 000000)
+        --background HEX colour behind the maze in images, or none (default: FFFFFF)
+        --every N        steps of the algorithm between two gif frames (default: 1)
+        --delay N        hundredths of a second per gif frame (default: 4)
+    -v, --verbose        log every step of the algorithm on stderr
+    -h, --help           print this help";
+
+
+/// Ways to write the maze once every task is done.
+#[derive(Clone, Copy, PartialEq)]
+enum Snapshot {
+    Text,
+    Svg,
+    #[cfg(feature = "raster")]
+    Png,
+    Maze,
+    Code(Encoding)
+}
+
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Snapshot(Snapshot),
+    /// Frames taken while the tasks run.
+    #[cfg(feature = "raster")]
+    Gif
+}
+
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name.to_lowercase().as_str() {
+            "text" | "txt" => Some(Format::Snapshot(Snapshot::Text)),
+            "svg" => Some(Format::Snapshot(Snapshot::Svg)),
+            #[cfg(feature = "raster")]
+            "png" => Some(Format::Snapshot(Snapshot::Png)),
+            #[cfg(feature = "raster")]
+            "gif" => Some(Format::Gif),
+            "maze" => Some(Format::Snapshot(Snapshot::Maze)),
+            "code" => Some(Format::Snapshot(Snapshot::Code(Encoding::Base64))),
+            "hex" => Some(Format::Snapshot(Snapshot::Code(Encoding::Hex))),
+            _ => None
+        }
+    }
+
+    fn from_path(path: &str) -> Option<Format> {
+        Path::new(path).extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Format::from_name)
+    }
+}
+
+
+struct Options {
+    algo: Algo,
+    columns: usize,
+    lines: usize,
+    seed: Seed,
+    input: Option<String>,
+    code: Option<(String, Encoding)>,
+    output: Option<String>,
+    tuning: Tuning,
+    format: Option<Format>,
+    charset: Charset,
+    depth: bool,
+    sources: Option<Vec<Address>>,
+    voronoi: bool,
+    status: bool,
+    solver: Option<Solver>,
+    heuristic: Option<Heuristic>,
+    hand: Option<Hand>,
+    from: Option<Address>,
+    to: Option<Address>,
+    cell_size: f64,
+    line_thickness: f64,
+    wall_color: Rgba,
+    background: Option<Rgba>,
+    every: usize,
+    delay: u16,
+    verbose: bool
+}
+
+
+impl Options {
+    fn parse<I>(mut args: I) -> Result<Options, String>
+        where I: Iterator<Item = String>
+    {
+        let mut options = Options {
+            algo: Algo::Wilson,
+            columns: 20,
+            lines: 10,
+            seed: rand::random(),
+            input: None,
+            code: None,
+            output: None,
+            tuning: Tuning::new(),
+            format: None,
+            charset: Charset::Ascii,
+            depth: false,
+            sources: None,
+            voronoi: false,
+            status: false,
+            solver: None,
+            heuristic: None,
+            hand: None,
+            from: None,
+            to: None,
+            cell_size: 10.0,
+            line_thickness: 1.0,
+            wall_color: palette::BLACK,
+            background: Some(palette::WHITE),
+            every: 1,
+            delay: 4,
+            verbose: false
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-a" | "--algo" => {
+                    let name = Self::value(&arg, args.next())?;
+                    options.algo = Algo::from_name(&name)
+                        .filter(|type_| !type_.is_endless())
+                        .ok_or_else(|| format!("unknown algorithm {}, expected one of: {}",
+                                               name, Self::algo_names()))?;
+                }
+                "-s" | "--size" => {
+                    let size = Self::value(&arg, args.next())?;
+                    let (columns, lines) = Self::parse_size(&size)?;
+                    options.columns = columns;
+                    options.lines = lines;
+                }
+                "-r" | "--seed" => {
+                    let seed = Self::value(&arg, args.next())?;
+                    options.seed = seed.parse()
+                        .map_err(|_| format!("invalid seed {}", seed))?;
+                }
+                "-i" | "--input" => {
+                    options.input = Some(Self::value(&arg, args.next())?);
+                }
+                "--code" => {
+                    options.code = Some((Self::value(&arg, args.next())?, Encoding::Base64));
+                }
+                "--hex-code" => {
+                    options.code = Some((Self::value(&arg, args.next())?, Encoding::Hex));
+                }
+                "-o" | "--output" => {
+                    options.output = Some(Self::value(&arg, args.next())?);
+                }
+                "--bias" => {
+                    let bias = Self::value(&arg, args.next())?;
+                    options.tuning.bias = bias.parse()
+                        .ok()
+                        .filter(|bias| *bias >= 0.0 && *bias <= 1.0)
+                        .ok_or_else(|| format!("invalid bias {}, expected 0 to 1", bias))?;
+                }
+                "--corner" => {
+                    let corner = Self::value(&arg, args.next())?;
+                    options.tuning.corner = Corner::from_name(&corner)
+                        .ok_or_else(|| format!("invalid corner {}, expected NE, NW, SE or SW",
+                                               corner))?;
+                }
+                "-f" | "--format" => {
+                    let format = Self::value(&arg, args.next())?;
+                    options.format = Some(Format::from_name(&format)
+                        .ok_or_else(|| format!("invalid format {}, expected text, svg, png, gif, maze, code or hex",
+                                               format))?);
+                }
+                "-c" | "--charset" => {
+                    let charset = Self::value(&arg, args.next())?;
+                    options.charset = Charset::from_name(&charset)
+                        .ok_or_else(|| format!("invalid charset {}, expected ascii or unicode",
+                                               charset))?;
+                }
+                "--depth" => options.depth = true,
+                "--sources" => {
+                    let sources = Self::value(&arg, args.next())?;
+                    options.depth = true;
+                    options.sources = Some(sources.split(',')
+                        .map(Self::parse_address)
+                        .collect::<Result<Vec<Address>, String>>()?);
+                }
+                "--voronoi" => {
+                    options.depth = true;
+                    options.voronoi = true;
+                }
+                "--status" => options.status = true,
+                "--solve" => {
+                    options.solver = options.solver.or(Some(Solver::BreadthFirst));
+                }
+                "--solver" => {
+                    let name = Self::value(&arg, args.next())?;
+                    options.solver = Some(Solver::from_name(&name)
+                        .ok_or_else(|| format!("unknown solver {}, expected one of: {}",
+                                               name, Self::solver_names()))?);
+                }
+                "--hand" => {
+                    let name = Self::value(&arg, args.next())?;
+                    options.hand = Some(Hand::from_name(&name)
+                        .ok_or_else(|| format!("invalid hand {}, expected left or right", name))?);
+                }
+                "--from" => {
+                    options.from = Some(Self::parse_address(&Self::value(&arg, args.next())?)?);
+                }
+                "--to" => {
+                    options.to = Some(Self::parse_address(&Self::value(&arg, args.next())?)?);
+                }
+                "--heuristic" => {
+                    let name = Self::value(&arg, args.next())?;
+                    options.heuristic = Some(Heuristic::from_name(&name)
+                        .ok_or_else(|| format!("invalid heuristic {}, expected manhattan, euclidean or zero",
+                                               name))?);
+                }
+                "--cell-size" => {
+                    let size = Self::value(&arg, args.next())?;
+                    options.cell_size = Self::parse_length(&size)?;
+                }
+                "--line-thickness" => {
+                    let thickness = Self::value(&arg, args.next())?;
+                    options.line_thickness = Self::parse_length(&thickness)?;
+                }
+                "--wall-color" => {
+                    let color = Self::value(&arg, args.next())?;
+                    options.wall_color = Self::parse_color(&color)?;
+                }
+                "--background" => {
+                    let color = Self::value(&arg, args.next())?;
+                    options.background = if color == "none" {
+                        None
+                    } else {
+                        Some(Self::parse_color(&color)?)
+                    };
+                }
+                "--every" => {
+                    let every = Self::value(&arg, args.next())?;
+                    options.every = every.parse()
+                        .ok()
+                        .filter(|every| *every > 0)
+                        .ok_or_else(|| format!("invalid step count {}", every))?;
+                }
+                "--delay" => {
+                    let delay = Self::value(&arg, args.next())?;
+                    options.delay = delay.parse()
+                        .map_err(|_| format!("invalid delay {}", delay))?;
+                }
+                "-v" | "--verbose" => options.verbose = true,
+                "-h" | "--help" => return Err(String::new()),
+                _ => return Err(format!("unknown option {}", arg))
+            }
+        }
+
+        // a loaded maze brings its own size, checked once it is read
+        if options.input.is_none() && options.code.is_none() {
+            options.check_cells(options.columns, options.lines)?;
+        }
+
+        Ok(options)
+    }
+
+    fn check_cells(&self, columns: usize, lines: usize) -> Result<(), String> {
+        let sources = self.sources.iter().flat_map(|sources| sources.iter());
+        for address in self.from.iter().chain(self.to.iter()).chain(sources) {
+            if address.column >= columns || address.line >= lines {
+                return Err(format!("cell {}:{} is outside of the {}x{} maze",
+                                   address.column, address.line, columns, lines));
+            }
+        }
+        Ok(())
+    }
+
+    fn value(arg: &str, value: Option<String>) -> Result<String, String> {
+        value.ok_or_else(|| format!("missing value after {}", arg))
+    }
+
+    fn parse_size(size: &str) -> Result<(usize, usize), String> {
+        let mut parts = size.splitn(2, &['x', 'X'][..]);
+        let columns = parts.next().and_then(|part| part.parse().ok());
+        let lines = parts.next().and_then(|part| part.parse().ok());
+
+        match (columns, lines) {
+            (Some(columns), Some(lines)) if columns > 0 && lines > 0 => Ok((columns, lines)),
+            _ => Err(format!("invalid size {}, expected WxH like 20x10", size))
+        }
+    }
+
+    fn parse_address(address: &str) -> Result<Address, String> {
+        let mut parts = address.splitn(2, ':');
+        let column = parts.next().and_then(|part| part.parse().ok());
+        let line = parts.next().and_then(|part| part.parse().ok());
+
+        match (column, line) {
+            (Some(column), Some(line)) => Ok(Address { column, line }),
+            _ => Err(format!("invalid cell {}, expected C:L like 0:0", address))
+        }
+    }
+
+    fn parse_length(length: &str) -> Result<f64, String> {
+        length.parse()
+            .ok()
+            .filter(|length: &f64| *length > 0.0 && length.is_finite())
+            .ok_or_else(|| format!("invalid length {}, expected a positive number", length))
+    }
+
+    fn parse_color(color: &str) -> Result<Rgba, String> {
+        palette::parse_hex(color)
+            .ok_or_else(|| format!("invalid colour {}, expected RRGGBB or RRGGBBAA", color))
+    }
+
+    fn format(&self) -> Format {
+        self.format
+            .or_else(|| self.output.as_ref().and_then(|path| Format::from_path(path)))
+            .unwrap_or(Format::Snapshot(Snapshot::Text))
+    }
+
+    fn solver(&self) -> Option<Solver> {
+        match (self.solver.clone(), self.heuristic, self.hand) {
+            (Some(Solver::AStar(_)), Some(heuristic), _) => Some(Solver::AStar(heuristic)),
+            (Some(Solver::WallFollower(_)), _, Some(hand)) => Some(Solver::WallFollower(hand)),
+            (solver, _, _) => solver
+        }
+    }
+
+    fn solver_names() -> String {
+        let mut names : Vec<&str> = Solver::all().iter()
+            .map(|type_| type_.name())
+            .collect();
+        names.dedup();
+        names.join(", ")
+    }
+
+    fn algo_names() -> String {
+        Algo::all().iter()
+            .filter(|type_| !type_.is_endless())
+            .map(|type_| type_.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+
+fn render(options: &Options, snapshot: Snapshot, maze: &OrthoMaze, highmap: &OrthoHighMap)
+    -> Vec<u8>
+{
+    match snapshot {
+        Snapshot::Text => {
+            let mut renderer = TextMazeRenderer::new();
+            renderer.set_charset(options.charset);
+            if options.depth {
+                renderer.toggle_highmap();
+            }
+            if options.status {
+                renderer.toggle_status();
+            }
+            renderer.render(maze, highmap).into_bytes()
+        }
+        Snapshot::Svg => {
+            let mut renderer = SvgMazeRenderer::new();
+            renderer.set_cell_size(options.cell_size);
+            renderer.set_line_thickness(options.line_thickness);
+            renderer.set_line_colors(options.wall_color, options.wall_color);
+            renderer.set_background(options.background);
+            renderer.set_cell_fill(cell_fill(options));
+            renderer.render(maze, highmap).into_bytes()
+        }
+        #[cfg(feature = "raster")]
+        Snapshot::Png => {
+            raster_renderer(options).render(maze, highmap).to_png()
+        }
+        Snapshot::Maze => {
+            let mut data = Vec::new();
+            let highmap = if options.depth { Some(highmap) } else { None };
+            archive::save(&mut data, maze, highmap)
+                .expect("saving a maze in memory");
+            data
+        }
+        Snapshot::Code(encoding) => {
+            let mut text = code::encode(maze, encoding);
+            text.push('\n');
+            text.into_bytes()
+        }
+    }
+}
+
+
+#[cfg(feature = "raster")]
+fn raster_renderer(options: &Options) -> RasterMazeRenderer {
+    let mut renderer = RasterMazeRenderer::new();
+    renderer.set_cell_size(options.cell_size);
+    renderer.set_line_thickness(options.line_thickness);
+    renderer.set_line_colors(options.wall_color, options.wall_color);
+    renderer.set_background(options.background);
+    renderer.set_cell_fill(cell_fill(options));
+    renderer
+}
+
+
+fn cell_fill(options: &Options) -> CellFill {
+    CellFill { highmap: options.depth, voronoi: options.voronoi, ..CellFill::default() }
+}
+
+
+#[cfg(feature = "raster")]
+fn record(options: &Options, tasks: &mut task::Executor<Args>, args: Args)
+    -> io::Result<(task::Status, Vec<u8>)>
+{
+    let mut data = Vec::new();
+    let status = {
+        let mut recorder = GifRecorder::new(&mut data, raster_renderer(options),
+                                            &args.maze.borrow())?;
+        recorder.set_every(options.every);
+        recorder.set_delay(options.delay);
+        recorder.record(tasks, args)?
+    };
+
+    Ok((status, data))
+}
+
+
+fn read_maze(path: &str) -> Result<(OrthoMaze, Option<OrthoHighMap>), String> {
+    let mut data = Vec::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut data))
+        .map_err(|err| format!("cannot read {}, {}", path, err))?;
+
+    if data.starts_with(b"MAZING") {
+        return archive::load(&data[..])
+            .map_err(|err| format!("{}: {}", path, err));
+    }
+
+    let text = String::from_utf8(data)
+        .map_err(|_| format!("{}: neither a maze archive nor text", path))?;
+    text_parse::parse(&text)
+        .map(|maze| (maze, None))
+        .map_err(|err| format!("{}: {}", path, err))
+}
+
+
+fn main() {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(ref msg) if msg.is_empty() => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let loaded = match (&options.input, &options.code) {
+        (Some(path), _) => Some(read_maze(path)),
+        (_, Some((text, encoding))) => Some(code::decode(text, *encoding)
+            .map(|maze| (maze, None))
+            .map_err(|err| format!("invalid maze code, {}", err))),
+        _ => None
+    };
+
+    let is_loaded = loaded.is_some();
+    let (blank, loaded_highmap) = match loaded {
+        Some(Ok(loaded)) => loaded,
+        Some(Err(msg)) => {
+            eprintln!("error: {}", msg);
+            process::exit(1);
+        }
+        None => {
+            eprintln!("[cli] {} {}x{}, seed {}",
+                      options.algo.name(), options.columns, options.lines, options.seed);
+            (options.algo.blank_maze(options.columns, options.lines), None)
+        }
+    };
+
+    let (columns, lines) = (blank.grid().columns(), blank.grid().lines());
+    if let Err(msg) = options.check_cells(columns, lines) {
+        eprintln!("error: {}", msg);
+        process::exit(1);
+    }
+    let maze = Rc::new(RefCell::new(blank));
+    let has_heights = loaded_highmap.is_some();
+    let highmap = Rc::new(RefCell::new(
+            loaded_highmap.unwrap_or_else(|| OrthoHighMap::new(columns, lines))));
+    let rng = Rc::new(RefCell::new(seeded_rng(options.seed)));
+
+    let mut tasks = task::Executor::new();
+    tasks.set_verbose(options.verbose);
+    if !is_loaded {
+        tasks.stack(options.algo.create(&*maze.borrow(), &options.tuning));
+    }
+    if options.depth && !has_heights {
+        let walker = match options.sources {
+            Some(ref sources) => DijkstraWalk::from_sources(sources.clone()),
+            None => DijkstraWalk::new(&*maze.borrow())
+        };
+        tasks.stack(Box::new(walker));
+    }
+    if let Some(solver) = options.solver() {
+        let start = options.from.clone().unwrap_or(Address { column: 0, line: 0 });
+        let goal = options.to.clone()
+            .unwrap_or(Address { column: columns - 1, line: lines - 1 });
+        tasks.stack(solver.create(&*maze.borrow(), start, goal));
+    }
+
+    let args = Args { maze: maze.clone(), highmap: highmap.clone(), rng };
+    let (status, bytes) = match options.format() {
+        #[cfg(feature = "raster")]
+        Format::Gif => match record(&options, &mut tasks, args) {
+            Ok(recorded) => recorded,
+            Err(err) => {
+                eprintln!("error: cannot record the maze, {}", err);
+                process::exit(1);
+            }
+        },
+        Format::Snapshot(snapshot) => {
+            let status = tasks.run(args);
+            (status, render(&options, snapshot, &maze.borrow(), &highmap.borrow()))
+        }
+    };
+
+    if let task::Status::Aborted(why) = status {
+        eprintln!("error: generation aborted, {}", why);
+        process::exit(1);
+    }
+
+    let written = match options.output {
+        Some(ref path) => File::create(path)
+            .and_then(|mut file| file.write_all(&bytes)),
+        None => io::stdout().write_all(&bytes)
+    };
+
+    if let Err(err) = written {
+        eprintln!("error: cannot write the maze, {}", err);
+        process::exit(1);
+    }
+}
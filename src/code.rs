@@ -0,0 +1,333 @@
+//! Short codes describing the gates of a maze, for URLs and save slots.
+//!
+//! The packed form starts with the columns and the lines as LEB128 varints,
+//! followed by 2 bits per cell, 4 cells per byte from the lowest bits: the
+//! first bit tells if the cell opens on its right, the second if it opens
+//! downward. Cells are stored line after line and unused bits are zero.
+
+use std::fmt;
+
+use super::grid::Within;
+use super::maze::OrthoMaze;
+
+
+const BASE64_URL : &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const HEX : &[u8; 16] = b"0123456789abcdef";
+
+const GATE_RIGHT : u8 = 0b01;
+const GATE_DOWN : u8 = 0b10;
+
+/// Same limit as the archives, a code is not meant for huge mazes anyway.
+const MAX_CELLS : usize = 1 << 26;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    /// URL safe base64 without padding.
+    Base64,
+    Hex
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodeError {
+    InvalidCharacter(usize),
+    Truncated,
+    InvalidSize,
+    TooLarge(usize, usize),
+    InvalidGate(usize, usize),
+    TrailingData
+}
+
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CodeError::InvalidCharacter(at) => write!(f, "invalid character at {}", at + 1),
+            CodeError::Truncated => write!(f, "code is truncated"),
+            CodeError::InvalidSize => write!(f, "invalid maze size"),
+            CodeError::TooLarge(columns, lines) =>
+                write!(f, "maze of {}x{} cells is too large", columns, lines),
+            CodeError::InvalidGate(column, line) =>
+                write!(f, "gate of {}:{} opens outside of the maze", column, line),
+            CodeError::TrailingData => write!(f, "unexpected data after the last cell")
+        }
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+pub fn pack(maze: &OrthoMaze) -> Vec<u8> {
+    let columns = maze.grid().columns();
+    let lines = maze.grid().lines();
+
+    let mut bytes = Vec::with_capacity(4 + (columns * lines).div_ceil(4));
+    put_varint(&mut bytes, columns);
+    put_varint(&mut bytes, lines);
+
+    for (index, pos) in maze.grid().iter().enumerate() {
+        let mut gate = 0;
+        if pos.can_move_right() {
+            gate |= GATE_RIGHT;
+        }
+        if pos.can_move_down() {
+            gate |= GATE_DOWN;
+        }
+
+        if index % 4 == 0 {
+            bytes.push(0);
+        }
+        *bytes.last_mut().expect("byte of the cell exists") |= gate << (2 * (index % 4));
+    }
+
+    bytes
+}
+
+
+pub fn unpack(bytes: &[u8]) -> Result<OrthoMaze, CodeError> {
+    let mut at = 0;
+    let columns = take_varint(bytes, &mut at)?;
+    let lines = take_varint(bytes, &mut at)?;
+
+    let cells = columns.checked_mul(lines)
+        .filter(|cells| *cells <= MAX_CELLS)
+        .ok_or(CodeError::TooLarge(columns, lines))?;
+
+    let gates = &bytes[at..];
+    let needed = cells.div_ceil(4);
+    if gates.len() < needed {
+        return Err(CodeError::Truncated);
+    } else if gates.len() > needed {
+        return Err(CodeError::TrailingData);
+    }
+
+    let mut maze = OrthoMaze::new(columns, lines);
+    for index in 0..cells {
+        let (column, line) = (index % columns, index / columns);
+        let gate = (gates[index / 4] >> (2 * (index % 4))) & (GATE_RIGHT | GATE_DOWN);
+
+        let right = gate & GATE_RIGHT != 0;
+        let down = gate & GATE_DOWN != 0;
+        if (right && column + 1 == columns) || (down && line + 1 == lines) {
+            return Err(CodeError::InvalidGate(column, line));
+        }
+
+        if right {
+            maze.carve(column, line, column + 1, line);
+        }
+        if down {
+            maze.carve(column, line, column, line + 1);
+        }
+    }
+
+    if cells % 4 != 0 && gates[needed - 1] >> (2 * (cells % 4)) != 0 {
+        return Err(CodeError::TrailingData);
+    }
+
+    Ok(maze)
+}
+
+
+pub fn encode(maze: &OrthoMaze, encoding: Encoding) -> String {
+    let bytes = pack(maze);
+
+    match encoding {
+        Encoding::Base64 => to_base64(&bytes),
+        Encoding::Hex => to_hex(&bytes)
+    }
+}
+
+
+pub fn decode(code: &str, encoding: Encoding) -> Result<OrthoMaze, CodeError> {
+    let bytes = match encoding {
+        Encoding::Base64 => from_base64(code.trim())?,
+        Encoding::Hex => from_hex(code.trim())?
+    };
+
+    unpack(&bytes)
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+fn put_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+
+fn take_varint(bytes: &[u8], at: &mut usize) -> Result<usize, CodeError> {
+    let mut value : usize = 0;
+    let mut shift = 0;
+
+    loop {
+        let byte = *bytes.get(*at).ok_or(CodeError::Truncated)?;
+        *at += 1;
+
+        if shift >= 32 {
+            return Err(CodeError::InvalidSize);
+        }
+        value |= ((byte & 0x7f) as usize) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+}
+
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity((bytes.len() * 4).div_ceil(3));
+
+    for chunk in bytes.chunks(3) {
+        let word = chunk.iter()
+            .enumerate()
+            .fold(0u32, |word, (index, byte)| word | (*byte as u32) << (16 - 8 * index));
+
+        for sextet in 0..chunk.len() + 1 {
+            let index = (word >> (18 - 6 * sextet)) & 0x3f;
+            code.push(BASE64_URL[index as usize] as char);
+        }
+    }
+
+    code
+}
+
+
+fn from_base64(code: &str) -> Result<Vec<u8>, CodeError> {
+    let mut bytes = Vec::with_capacity(code.len() * 3 / 4);
+    let mut word : u32 = 0;
+    let mut bits = 0;
+
+    for (at, c) in code.bytes().enumerate() {
+        let value = BASE64_URL.iter()
+            .position(|digit| *digit == c)
+            .ok_or(CodeError::InvalidCharacter(at))?;
+
+        word = (word << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((word >> bits) as u8);
+            word &= (1 << bits) - 1;
+        }
+    }
+
+    // a lone sextet cannot hold a byte, left over bits must be padding
+    if bits == 6 {
+        return Err(CodeError::Truncated);
+    } else if word != 0 {
+        return Err(CodeError::TrailingData);
+    }
+
+    Ok(bytes)
+}
+
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        code.push(HEX[(byte >> 4) as usize] as char);
+        code.push(HEX[(byte & 0x0f) as usize] as char);
+    }
+    code
+}
+
+
+fn from_hex(code: &str) -> Result<Vec<u8>, CodeError> {
+    let digits = code.bytes()
+        .enumerate()
+        .map(|(at, c)| {
+            (c as char).to_digit(16)
+                .map(|digit| digit as u8)
+                .ok_or(CodeError::InvalidCharacter(at))
+        })
+        .collect::<Result<Vec<u8>, CodeError>>()?;
+
+    if digits.len() % 2 != 0 {
+        return Err(CodeError::Truncated);
+    }
+
+    Ok(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maze of `columns` by `lines` cells walked line after line, going
+    /// down at alternate ends.
+    fn serpentine(columns: usize, lines: usize) -> OrthoMaze {
+        let mut maze = OrthoMaze::new(columns, lines);
+        for line in 0..lines {
+            for column in 0..columns.saturating_sub(1) {
+                maze.carve(column, line, column + 1, line);
+            }
+            if line + 1 < lines {
+                let column = if line % 2 == 0 { columns - 1 } else { 0 };
+                maze.carve(column, line, column, line + 1);
+            }
+        }
+        maze
+    }
+
+    fn gates(maze: &OrthoMaze) -> Vec<(bool, bool)> {
+        maze.grid().iter()
+            .map(|pos| (pos.can_move_right(), pos.can_move_down()))
+            .collect()
+    }
+
+    #[test]
+    fn decodes_encoded_mazes_back() {
+        for &(columns, lines) in &[(1, 1), (2, 1), (3, 2), (5, 3), (7, 7), (200, 3)] {
+            let maze = serpentine(columns, lines);
+
+            for &encoding in &[Encoding::Base64, Encoding::Hex] {
+                let code = encode(&maze, encoding);
+                let decoded = decode(&code, encoding).expect("encoded maze decodes");
+
+                assert_eq!(decoded.grid().columns(), columns);
+                assert_eq!(decoded.grid().lines(), lines);
+                assert_eq!(gates(&decoded), gates(&maze), "{:?} code {}", encoding, code);
+            }
+        }
+    }
+
+    #[test]
+    fn packs_sizes_then_two_bits_per_cell() {
+        let maze = serpentine(2, 2);
+
+        assert_eq!(pack(&maze), vec![2, 2, 0b00_01_10_01]);
+        assert_eq!(encode(&maze, Encoding::Hex), "020219");
+    }
+
+    #[test]
+    fn reports_invalid_characters() {
+        let code = encode(&serpentine(3, 2), Encoding::Base64);
+        let broken = format!("{}*", &code[..2]);
+
+        assert_eq!(decode(&broken, Encoding::Base64).err(), Some(CodeError::InvalidCharacter(2)));
+        assert_eq!(decode("02g2", Encoding::Hex).err(), Some(CodeError::InvalidCharacter(2)));
+    }
+
+    #[test]
+    fn refuses_truncated_codes() {
+        let code = encode(&serpentine(5, 3), Encoding::Hex);
+
+        assert_eq!(decode(&code[..code.len() - 2], Encoding::Hex).err(), Some(CodeError::Truncated));
+    }
+}
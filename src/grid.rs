@@ -20,6 +20,15 @@ impl<T> Grid<T> where T: Default {
         }
         Grid{ columns, lines, cells }
     }
+
+    pub fn grow(&mut self, lines: usize) {
+        let count = lines * self.columns;
+        self.cells.reserve(count);
+        for _ in 0..count {
+            self.cells.push(T::default());
+        }
+        self.lines += lines;
+    }
 }
 
 
@@ -54,11 +63,11 @@ impl<T> Grid<T> {
         y < self.lines && x < self.columns
     }
 
-    fn localize(&self, x: usize, y: usize) -> usize {
+    pub fn localize(&self, x: usize, y: usize) -> usize {
         y * self.columns + x
     }
 
-    fn pin(&self, index: usize) -> (usize, usize) {
+    pub fn pin(&self, index: usize) -> (usize, usize) {
         let y = index / self.columns;
         let x = index - y * self.columns;
         (x, y)
@@ -104,34 +113,54 @@ impl<T> Grid<T> {
         self.cell(x, y)
     }
 
-    pub fn anywhere_rand(&self) -> Option<Pos<T>>
+    pub fn anywhere_rand<R>(&self, rng: &mut R) -> Option<Pos<T>>
+        where R: rand::Rng
     {
-        use self::rand::Rng;
+        if self.cell_count() == 0 {
+            return None;
+        }
 
-        let index = rand::thread_rng().gen_range(0, self.cell_count());
+        let index = rng.gen_range(0, self.cell_count());
         let (column, line) = self.pin(index);
         Some(Pos{ column, line, grid: self })
     }
 
-    pub fn anywhere_rand_match<F>(&self, func: F) 
+    pub fn anywhere_rand_match<R, F>(&self, rng: &mut R, func: F) 
         -> Option<Pos<T>> 
-        where F: Fn(&Pos<T>) -> bool
+        where R: rand::Rng, F: Fn(&Pos<T>) -> bool
     {
-        use self::rand::Rng;
+        if self.cell_count() == 0 {
+            return None;
+        }
 
-        let index = rand::thread_rng().gen_range(0, self.cell_count());
+        let index = rng.gen_range(0, self.cell_count());
         let (column, line) = self.pin(index);
         let mut candidate = Pos{ column, line, grid: self };
 
         let mut security = self.cell_count();
         while security > 0 && !func(&candidate) {
-            let index = rand::thread_rng().gen_range(0, self.cell_count());
+            let index = rng.gen_range(0, self.cell_count());
             let (column, line) = self.pin(index);
             candidate = Pos{ column, line, grid: self };
             security -= 1;
         }
 
-        if security == 0 { None } else { Some(candidate) }
+        if security > 0 {
+            return Some(candidate);
+        }
+
+        // Random probing gave up, fall back on the matching cells left.
+        let matching : Vec<usize> = (0..self.cell_count())
+            .filter(|index| {
+                let (column, line) = self.pin(*index);
+                func(&Pos{ column, line, grid: self })
+            })
+            .collect();
+
+        rng.choose(&matching).map(|index| {
+            let (column, line) = self.pin(*index);
+            Pos{ column, line, grid: self }
+        })
     }
 }
 
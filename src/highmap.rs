@@ -3,14 +3,17 @@ use super::grid::{Grid, Within, Pos, PosMut};
 
 #[derive(Debug)]
 pub struct CellStatus {
-    height: Option<usize>
+    height: Option<usize>,
+    /// Index of the source the height is measured from.
+    source: Option<usize>
 }
 
 
 impl Default for CellStatus {
     fn default() -> CellStatus {
         CellStatus{ 
-            height: None
+            height: None,
+            source: None
         }
     }
 }
@@ -32,6 +35,10 @@ impl OrthoHighMap {
             highest: 0
         }
     }
+
+    pub fn grow(&mut self, lines: usize) {
+        self.grid.grow(lines);
+    }
 }
 
 
@@ -58,6 +65,11 @@ impl<'a> Pos<'a, CellStatus> {
             .map(|cell| cell.height)
             .unwrap_or(None)
     }
+
+    pub fn source(&self) -> Option<usize> {
+        self.grid.at(self.column, self.line)
+            .and_then(|cell| cell.source)
+    }
 }
     
 impl<'a> PosMut<'a, CellStatus> {
@@ -66,4 +78,10 @@ impl<'a> PosMut<'a, CellStatus> {
             seed.height = Some(height);
         }
     }
+
+    pub fn set_source(&mut self, source: usize) {
+        if let Some(ref mut seed) = self.grid.at_mut(self.column, self.line) {
+            seed.source = Some(source);
+        }
+    }
 }
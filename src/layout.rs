@@ -0,0 +1,85 @@
+/// Geometry of a maze drawn as square cells separated by walls, with the
+/// origin on the centre of the maze.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout {
+    pub cell_size: f64,
+    pub line_thickness: f64
+}
+
+
+impl Layout {
+    pub fn new(cell_size: f64, line_thickness: f64) -> Layout {
+        Layout { cell_size, line_thickness }
+    }
+
+    /// Distance between two neighbour cells.
+    pub fn space(&self) -> f64 {
+        self.cell_size + self.line_thickness
+    }
+
+    /// Upper left corner, width and height of the frame around the maze.
+    pub fn frame_box(&self, columns: usize, lines: usize) -> (f64, f64, f64, f64) {
+        let space = self.space();
+
+        let width = columns as f64 * space + self.line_thickness;
+        let height = lines as f64 * space + self.line_thickness;
+
+        let origin_x = (width - self.line_thickness) * 0.5;
+        let origin_y = (height - self.line_thickness) * 0.5;
+
+        (-origin_x, -origin_y, width, height)
+    }
+
+    /// Shift bringing the frame box to start at 0, 0 like image pixels do.
+    pub fn image_offset(&self, columns: usize, lines: usize) -> (f64, f64) {
+        let (origin_x, origin_y, _, _) = self.frame_box(columns, lines);
+        let hlt = self.line_thickness * 0.5;
+
+        (hlt - origin_x, hlt - origin_y)
+    }
+
+    /// Boxes drawing the path through the cell of upper left corner
+    /// `corner_x`, `corner_y`: a square on its centre, extended up to the
+    /// centre of the next cell on the right or below when the path goes on.
+    pub fn path_boxes(&self, corner_x: f64, corner_y: f64, right: bool, down: bool)
+        -> Vec<(f64, f64, f64, f64)>
+    {
+        let space = self.space();
+        let thickness = (self.cell_size * 0.4).max(self.line_thickness);
+        let center_x = corner_x + space * 0.5;
+        let center_y = corner_y + space * 0.5;
+        let hpt = thickness * 0.5;
+
+        let mut boxes = vec![(center_x - hpt, center_y - hpt, thickness, thickness)];
+        if right {
+            boxes.push((center_x, center_y - hpt, space, thickness));
+        }
+        if down {
+            boxes.push((center_x - hpt, center_y, thickness, space));
+        }
+        boxes
+    }
+
+    /// Dots drawing `count` marks on the passage leaving the cell of upper
+    /// left corner `corner_x`, `corner_y` to the right, or downward when
+    /// `down` is set. The dots line up along the wall they cross.
+    pub fn mark_boxes(&self, corner_x: f64, corner_y: f64, down: bool, count: u8)
+        -> Vec<(f64, f64, f64, f64)>
+    {
+        let space = self.space();
+        let size = (self.cell_size * 0.25).max(self.line_thickness);
+        let gap = size * 1.5;
+
+        let (center_x, center_y) = if down {
+            (corner_x + space * 0.5, corner_y + space)
+        } else {
+            (corner_x + space, corner_y + space * 0.5)
+        };
+
+        (0..count)
+            .map(|index| (index as f64 - (count as f64 - 1.0) * 0.5) * gap)
+            .map(|shift| if down { (center_x + shift, center_y) } else { (center_x, center_y + shift) })
+            .map(|(x, y)| (x - size * 0.5, y - size * 0.5, size, size))
+            .collect()
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(feature = "gui")]
+extern crate piston;
+#[cfg(feature = "gui")]
+extern crate graphics;
+#[cfg(feature = "gui")]
+extern crate opengl_graphics;
+
+
+pub mod settings;
+pub mod task;
+pub mod grid;
+pub mod maze;
+pub mod highmap;
+pub mod text_render;
+pub mod text_parse;
+pub mod palette;
+pub mod layout;
+pub mod svg_render;
+#[cfg(feature = "raster")]
+pub mod raster;
+#[cfg(feature = "raster")]
+pub mod record;
+pub mod archive;
+pub mod code;
+pub mod algo;
+
+#[cfg(feature = "gui")]
+pub mod app;
+#[cfg(feature = "gui")]
+pub mod maze_render;
@@ -1,29 +1,18 @@
-#[macro_use]
-extern crate lazy_static;
+extern crate mazing;
 extern crate piston;
-extern crate graphics;
 extern crate glutin_window;
 extern crate opengl_graphics;
 
 
-use piston::window::WindowSettings;
+use piston::window::{AdvancedWindow, WindowSettings};
 use piston::event_loop::{Events, EventSettings};
 use piston::input::{keyboard, RenderEvent, UpdateEvent, PressEvent};
 use glutin_window::GlutinWindow;
 use opengl_graphics::{ GlGraphics, OpenGL };
 
 
-pub mod app;
-pub mod settings;
-pub mod task;
-pub mod grid;
-pub mod maze;
-pub mod maze_render;
-pub mod highmap;
-pub mod algo;
-
-
-use settings::{DEBUG_GATE, DEBUG_ALGO, DEBUG_MAIN};
+use mazing::app;
+use mazing::settings::{DEBUG_GATE, DEBUG_ALGO, DEBUG_MAIN};
 
 
 fn main() {
@@ -50,6 +39,7 @@ fn main() {
 
     let mut events = Events::new(EventSettings::new());
     let mut modkeys = keyboard::ModifierKey::NO_MODIFIER;
+    let mut title = String::new();
     while let Some(e) = events.next(&mut window) {
         if DEBUG_MAIN {
             println!("[main] start-loop, pre-render");
@@ -73,6 +63,12 @@ fn main() {
         if let Some(b) = e.press_args() {
             app.button_pressed(&b, &modkeys);
         }
+
+        let app_title = app.title();
+        if app_title != title {
+            window.set_title(app_title.clone());
+            title = app_title;
+        }
         
         if DEBUG_MAIN {
             println!("[main] post-button-pressed, end-loop");
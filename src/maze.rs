@@ -21,7 +21,17 @@ pub struct CellStatus {
     open_gate_vert: bool,
     active: bool,
     current: bool,
-    visited: bool
+    visited: bool,
+    path: bool,
+    filled: bool,
+    /// Trémaux marks on the passages to the right and below, up to 2.
+    marks_hori: u8,
+    marks_vert: u8,
+    /// Search of a bidirectional solver that reached the cell, 0 for the
+    /// one from the start and 1 for the one from the goal.
+    frontier: Option<usize>,
+    meeting: bool,
+    group: Option<usize>
 }
 
 
@@ -32,7 +42,14 @@ impl Default for CellStatus {
             open_gate_vert: false,
             active: false,
             current: false,
-            visited: false
+            visited: false,
+            path: false,
+            filled: false,
+            marks_hori: 0,
+            marks_vert: 0,
+            frontier: None,
+            meeting: false,
+            group: None
         }
     }
 }
@@ -51,6 +68,21 @@ impl OrthoMaze {
         OrthoMaze { grid: Grid::new(w, h) }
     }
 
+    pub fn new_open(w: usize, h: usize) -> OrthoMaze {
+        let mut grid : Grid<CellStatus> = Grid::new(w, h);
+        for address in grid.crumbs() {
+            if let Some(ref mut cell) = grid.at_mut(address.column, address.line) {
+                cell.open_gate_hori = address.column + 1 < w;
+                cell.open_gate_vert = address.line + 1 < h;
+            }
+        }
+        OrthoMaze { grid }
+    }
+
+    pub fn grow(&mut self, lines: usize) {
+        self.grid.grow(lines);
+    }
+
     fn continuity(
         &self,
         start_x: usize,
@@ -74,6 +106,34 @@ impl OrthoMaze {
             }
         }
 
+    fn gate_mut(&mut self,
+                start_x: usize,
+                start_y: usize,
+                end_x: usize,
+                end_y: usize)
+        -> Option<&mut bool>
+    {
+        match self.continuity(start_x, start_y, end_x, end_y) {
+            Some(GateWay::VERTICAL) if start_y < end_y => {
+                self.grid.at_mut(start_x, start_y)
+                    .map(|cell| &mut cell.open_gate_vert)
+            }
+            Some(GateWay::VERTICAL) if end_y < start_y => {
+                self.grid.at_mut(end_x, end_y)
+                    .map(|cell| &mut cell.open_gate_vert)
+            }
+            Some(GateWay::HORIZONTAL) if start_x < end_x => {
+                self.grid.at_mut(start_x, start_y)
+                    .map(|cell| &mut cell.open_gate_hori)
+            }
+            Some(GateWay::HORIZONTAL) if end_x < start_x => {
+                self.grid.at_mut(end_x, end_y)
+                    .map(|cell| &mut cell.open_gate_hori)
+            }
+            _ => None
+        }
+    }
+
     pub fn carve(&mut self,
                  start_x: usize,
                  start_y: usize,
@@ -86,33 +146,30 @@ impl OrthoMaze {
             return;
         }
 
-        match self.continuity(start_x, start_y, end_x, end_y) {
-            Some(GateWay::VERTICAL) if start_y < end_y => {
-                if let Some(ref mut cell) =
-                    self.grid.at_mut(start_x, start_y) {
-                        cell.open_gate_vert = true;
-                    }
-            }
-            Some(GateWay::VERTICAL) if end_y < start_y => {
-                if let Some(ref mut cell) =
-                    self.grid.at_mut(end_x, end_y) {
-                        cell.open_gate_vert = true;
-                    }
-            }
-            Some(GateWay::HORIZONTAL) if start_x < end_x => {
-                if let Some(ref mut cell) =
-                    self.grid.at_mut(start_x, start_y) {
-                        cell.open_gate_hori = true;
-                    }
-            }
-            Some(GateWay::HORIZONTAL) if end_x < start_x => {
-                if let Some(ref mut cell) =
-                    self.grid.at_mut(end_x, end_y) {
-                        cell.open_gate_hori = true;
-                    }
+        match self.gate_mut(start_x, start_y, end_x, end_y) {
+            Some(gate) => *gate = true,
+            None => println!("Failed to carve between {}:{} and {}:{}",
+                             start_x, start_y, end_x, end_y)
+        }
+    }
+
+    // false when the cells are not neighbours of the maze
+    pub fn wall(&mut self,
+                start_x: usize,
+                start_y: usize,
+                end_x: usize,
+                end_y: usize) -> bool
+    {
+        if !self.grid.contains(start_x, start_y) || !self.grid.contains(end_x, end_y) {
+            return false;
+        }
+
+        match self.gate_mut(start_x, start_y, end_x, end_y) {
+            Some(gate) => {
+                *gate = false;
+                true
             }
-            _ => ( println!("Failed to carve between {}:{} and {}:{}",
-                            start_x, start_y, end_x, end_y) )
+            None => false
         }
     }
 
@@ -174,7 +231,7 @@ impl<'a> Pos<'a, CellStatus> {
 
     pub fn can_move_to(&self, pos: &Self) -> bool {
         if self.column == pos.column && self.line.wrapping_sub(1) == pos.line {
-            // `self` is bellow `pos`
+            // `self` is below `pos`
             pos.can_move_down()
         } else if self.column == pos.column && self.line == pos.line.wrapping_sub(1) {
             // `self` is above `pos`
@@ -192,64 +249,220 @@ impl<'a> Pos<'a, CellStatus> {
 
     pub fn can_move_down(&self) -> bool {
         self.grid.at(self.column, self.line)
-            .map(|ref cell| cell.open_gate_vert)
+            .map(|cell| cell.open_gate_vert)
             .unwrap_or(false)
     }
 
     pub fn can_move_right(&self) -> bool {
         self.grid.at(self.column, self.line)
-            .map(|ref cell| cell.open_gate_hori)
+            .map(|cell| cell.open_gate_hori)
             .unwrap_or(false)
     }
 
     pub fn is_active(&self) -> bool {
         self.grid.at(self.column, self.line)
-            .map(|ref cell| cell.active)
+            .map(|cell| cell.active)
             .unwrap_or(false)
     }
 
     pub fn is_current(&self) -> bool {
         self.grid.at(self.column, self.line)
-            .map(|ref cell| cell.current)
+            .map(|cell| cell.current)
             .unwrap_or(false)
     }
 
     pub fn is_visited(&self) -> bool {
         self.grid.at(self.column, self.line)
-            .map(|ref cell| cell.visited)
+            .map(|cell| cell.visited)
+            .unwrap_or(false)
+    }
+
+    pub fn is_on_path(&self) -> bool {
+        self.grid.at(self.column, self.line)
+            .map(|cell| cell.path)
+            .unwrap_or(false)
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.grid.at(self.column, self.line)
+            .map(|cell| cell.filled)
+            .unwrap_or(false)
+    }
+
+    pub fn frontier(&self) -> Option<usize> {
+        self.grid.at(self.column, self.line)
+            .and_then(|cell| cell.frontier)
+    }
+
+    pub fn is_meeting(&self) -> bool {
+        self.grid.at(self.column, self.line)
+            .map(|cell| cell.meeting)
+            .unwrap_or(false)
+    }
+
+    pub fn marks_right(&self) -> u8 {
+        self.grid.at(self.column, self.line)
+            .map(|cell| cell.marks_hori)
+            .unwrap_or(0)
+    }
+
+    pub fn marks_down(&self) -> u8 {
+        self.grid.at(self.column, self.line)
+            .map(|cell| cell.marks_vert)
+            .unwrap_or(0)
+    }
+
+    /// Marks on the passage between `self` and the neighbour `pos`.
+    pub fn marks_to(&self, pos: &Self) -> u8 {
+        if self.column == pos.column && self.line.wrapping_sub(1) == pos.line {
+            pos.marks_down()
+        } else if self.column == pos.column && self.line == pos.line.wrapping_sub(1) {
+            self.marks_down()
+        } else if self.line == pos.line && self.column.wrapping_sub(1) == pos.column {
+            pos.marks_right()
+        } else if self.line == pos.line && self.column == pos.column.wrapping_sub(1) {
+            self.marks_right()
+        } else {
+            0
+        }
+    }
+
+    /// Whether the path goes on to the cell at the right.
+    pub fn path_goes_right(&self) -> bool {
+        self.is_on_path() && self.can_move_right() && self.grid
+            .cell(self.column + 1, self.line)
+            .map(|pos| pos.is_on_path())
             .unwrap_or(false)
     }
+
+    /// Whether the path goes on to the cell below.
+    pub fn path_goes_down(&self) -> bool {
+        self.is_on_path() && self.can_move_down() && self.grid
+            .cell(self.column, self.line + 1)
+            .map(|pos| pos.is_on_path())
+            .unwrap_or(false)
+    }
+
+    pub fn group(&self) -> Option<usize> {
+        self.grid.at(self.column, self.line)
+            .and_then(|cell| cell.group)
+    }
 }
 
 
 impl<'a> PosMut<'a, CellStatus> {
     pub fn mark_current(&mut self) {
-        self.grid.at_mut(self.column, self.line)
-            .map(|ref mut cell| cell.current = true);
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.current = true;
+        }
     }
 
     pub fn unmark_current(&mut self) {
-        self.grid.at_mut(self.column, self.line)
-            .map(|ref mut cell| cell.current = false);
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.current = false;
+        }
     }
 
     pub fn mark_active(&mut self) {
-        self.grid.at_mut(self.column, self.line)
-            .map(|ref mut cell| cell.active = true);
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.active = true;
+        }
     }
 
     pub fn unmark_active(&mut self) {
-        self.grid.at_mut(self.column, self.line)
-            .map(|ref mut cell| cell.active = false);
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.active = false;
+        }
     }
 
     pub fn mark_visit(&mut self) {
-        self.grid.at_mut(self.column, self.line)
-            .map(|ref mut cell| cell.visited = true);
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.visited = true;
+        }
     }
 
     pub fn unmark_visit(&mut self) {
-        self.grid.at_mut(self.column, self.line)
-            .map(|ref mut cell| cell.visited = false);
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.visited = false;
+        }
+    }
+
+    pub fn mark_path(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.path = true;
+        }
+    }
+
+    pub fn unmark_path(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.path = false;
+        }
+    }
+
+    pub fn mark_filled(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.filled = true;
+        }
+    }
+
+    pub fn unmark_filled(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.filled = false;
+        }
+    }
+
+    pub fn mark_frontier(&mut self, side: usize) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.frontier = Some(side);
+        }
+    }
+
+    pub fn unmark_frontier(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.frontier = None;
+        }
+    }
+
+    pub fn mark_meeting(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.meeting = true;
+        }
+    }
+
+    pub fn unmark_meeting(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.meeting = false;
+        }
+    }
+
+    pub fn add_mark_right(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.marks_hori = (cell.marks_hori + 1).min(2);
+        }
+    }
+
+    pub fn add_mark_down(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.marks_vert = (cell.marks_vert + 1).min(2);
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.marks_hori = 0;
+            cell.marks_vert = 0;
+        }
+    }
+
+    pub fn mark_group(&mut self, group: usize) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.group = Some(group);
+        }
+    }
+
+    pub fn unmark_group(&mut self) {
+        if let Some(cell) = self.grid.at_mut(self.column, self.line) {
+            cell.group = None;
+        }
     }
 }
@@ -1,30 +1,16 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 
-use graphics::{color, Context, line, rectangle};
-use graphics::types::{Color, ColorComponent};
+use graphics::{color, Context, Transformed, line, rectangle};
+use graphics::types::Color;
 use opengl_graphics::{GlGraphics};
 
 use super::settings::{DEBUG_GATE, DEBUG_ALGO};
-use super::grid::{Pos, Within};
-use super::maze;
-use super::highmap;
+use super::grid::Within;
 use super::maze::OrthoMaze;
 use super::highmap::OrthoHighMap;
-
-
-lazy_static! {
-    static ref ROOT_COLORS : [Color; 5] = [
-        color::hex("1B5E20"), // green
-        color::hex("0D47A1"), // blue
-        color::hex("E65100"), // orange
-        color::hex("b71c1c"), // red
-        color::hex("004D40") // turquoise
-    ];
-}
-
-
-const DIST_PER_COLOR : usize = 80;
+use super::palette::{self, CellFill};
+use super::layout::Layout;
 
 
 // ----------------------------------------------------------------------------
@@ -42,6 +28,8 @@ pub trait MazeRenderer {
     
     fn toggle_highmap(&mut self);
 
+    fn toggle_voronoi(&mut self);
+
     fn distance_per_color(&mut self) -> usize;
     fn set_distance_per_color(&mut self, dist: usize);
 }
@@ -56,8 +44,7 @@ pub struct StaticMazeRenderer {
     hori_line: Color,
     vert_line: Color,
     visible_gates: bool,
-    visible_highmap: bool,
-    dist_per_color: usize
+    fill: CellFill
 }
 
 
@@ -87,62 +74,32 @@ impl StaticMazeRenderer {
             hori_line,
             vert_line,
             visible_gates: true,
-            visible_highmap: true,
-            dist_per_color: DIST_PER_COLOR
+            fill: CellFill { highmap: true, ..CellFill::default() }
         }
     }
 
-    fn current_status_color<'a>(
-        &'a self,
-        pos: Pos<'a, maze::CellStatus>)
-        -> Option<Color> {
-            if pos.is_current() {
-                Some(color::hex("FF5722"))
-            } else if pos.is_active() {
-                Some(color::hex("FFC107"))
-            } else if pos.is_visited() {
-                Some(color::hex("9E9E9E"))
-            } else {
-                None
-            }
-        }
-
-    
-    fn height_color<'a>(
-        &'a self,
-        pos: Pos<'a, highmap::CellStatus>,
-        _highest: usize)
-        -> Option<Color> {
-            pos.height().map(|v| self.altitude_color(v))
-        }
-
-    
-    fn altitude_color(&self, altitude: usize) -> Color {
-        use graphics::Colored;
-        
-        let color_index = (altitude / self.dist_per_color) % ROOT_COLORS.len();
-
-        let size = self.dist_per_color as f64;
-        let advance = (altitude as f64 % size) / size;
-        
-        let tint = 0.4 + 1.2 * advance;
-        ROOT_COLORS[color_index].tint(tint as ColorComponent)
-    }
-
-
     fn frame_box(&self, maze: Rc<RefCell<OrthoMaze>>)
         -> (f64, f64, f64, f64) {
             let maze = maze.borrow();
 
-            let space = self.cell_size + self.line_thickness;
+            Layout::new(self.cell_size, self.line_thickness)
+                .frame_box(maze.grid().columns(), maze.grid().lines())
+        }
 
-            let width = maze.grid().columns() as f64 * space + self.line_thickness;
-            let height = maze.grid().lines() as f64 * space + self.line_thickness;
 
-            let origin_x = (width - self.line_thickness) * 0.5;
-            let origin_y = (height - self.line_thickness) * 0.5;
+    /// Scrolls a maze taller than the view so that its last lines stay in
+    /// sight while an endless algorithm keeps adding more.
+    fn follow_last_lines(&self, maze: Rc<RefCell<OrthoMaze>>, context: &Context)
+        -> Context {
+            let (_, _, _, height) = self.frame_box(maze);
+            let view_height = context.get_view_size()[1];
 
-            (-origin_x, -origin_y, width, height)
+            let mut context = *context;
+            if height > view_height {
+                let shift = (height - view_height) * 0.5;
+                context.transform = context.transform.trans(0.0, -shift);
+            }
+            context
         }
 
 
@@ -246,11 +203,10 @@ impl StaticMazeRenderer {
 
         let maze = maze.borrow();
         let highmap = highmap.borrow();
-        let highest = highmap.highest;
 
         for address in maze.grid().crumbs() {
             let pos = address.from(&*maze).expect("position of maze exists");
-            let hpos = address.from(&*highmap).expect("position of highmap exists");
+            let hpos = address.from(&*highmap);
 
             let x = address.column;
             let y = address.line;
@@ -258,12 +214,7 @@ impl StaticMazeRenderer {
             let corner_x = origin_x + x as f64 * space;
             let corner_y = origin_y + y as f64 * space;
 
-            let status_color = self.current_status_color(pos);
-            let color = if self.visible_highmap {
-                status_color.or_else(|| self.height_color(hpos, highest))
-            } else {
-                status_color
-            };
+            let color = palette::cell_color(&pos, hpos.as_ref(), &self.fill);
 
             if let Some(color) = color {
                 rectangle(color, [
@@ -275,6 +226,58 @@ impl StaticMazeRenderer {
             }
         }
     }
+
+    fn draw_path_centered(
+        &mut self,
+        maze: Rc<RefCell<OrthoMaze>>,
+        context: &Context,
+        gl: &mut GlGraphics)
+    {
+        let (origin_x, origin_y, _, _) = self.frame_box(maze.clone());
+
+        let layout = Layout::new(self.cell_size, self.line_thickness);
+        let space = layout.space();
+        let path_color = palette::path_color();
+
+        let maze = maze.borrow();
+
+        for pos in maze.grid().iter().filter(|pos| pos.is_on_path()) {
+            let corner_x = origin_x + pos.column as f64 * space;
+            let corner_y = origin_y + pos.line as f64 * space;
+
+            let boxes = layout.path_boxes(corner_x, corner_y,
+                                          pos.path_goes_right(), pos.path_goes_down());
+            for (x, y, width, height) in boxes {
+                rectangle(path_color, [x, y, width, height], context.transform, gl);
+            }
+        }
+    }
+
+    fn draw_marks_centered(
+        &mut self,
+        maze: Rc<RefCell<OrthoMaze>>,
+        context: &Context,
+        gl: &mut GlGraphics)
+    {
+        let (origin_x, origin_y, _, _) = self.frame_box(maze.clone());
+
+        let layout = Layout::new(self.cell_size, self.line_thickness);
+        let space = layout.space();
+        let mark_color = palette::mark_color();
+
+        let maze = maze.borrow();
+
+        for pos in maze.grid().iter() {
+            let corner_x = origin_x + pos.column as f64 * space;
+            let corner_y = origin_y + pos.line as f64 * space;
+
+            let mut boxes = layout.mark_boxes(corner_x, corner_y, false, pos.marks_right());
+            boxes.extend(layout.mark_boxes(corner_x, corner_y, true, pos.marks_down()));
+            for (x, y, width, height) in boxes {
+                rectangle(mark_color, [x, y, width, height], context.transform, gl);
+            }
+        }
+    }
 }
 
 
@@ -286,13 +289,18 @@ impl MazeRenderer for StaticMazeRenderer {
         context: &Context,
         gl: &mut GlGraphics)
     {
+        let context = &self.follow_last_lines(maze.clone(), context);
+
         self.draw_cells_centered(maze.clone(), highmap.clone(), context, gl);
-        
+        self.draw_path_centered(maze.clone(), context, gl);
+
         self.draw_partial_frame_centered(maze.clone(), context, gl);
 
         if self.visible_gates {
             self.draw_gates_centered(maze.clone(), context, gl);
         }
+
+        self.draw_marks_centered(maze.clone(), context, gl);
     }
 
     fn toggle_gate(&mut self) {
@@ -300,14 +308,18 @@ impl MazeRenderer for StaticMazeRenderer {
     }
     
     fn toggle_highmap(&mut self) {
-        self.visible_highmap = !self.visible_highmap;
+        self.fill.highmap = !self.fill.highmap;
+    }
+
+    fn toggle_voronoi(&mut self) {
+        self.fill.voronoi = !self.fill.voronoi;
     }
     
     fn distance_per_color(&mut self) -> usize {
-        self.dist_per_color
+        self.fill.dist_per_color
     }
     
     fn set_distance_per_color(&mut self, dist: usize) {
-        self.dist_per_color = dist;
+        self.fill.dist_per_color = dist;
     }
 }
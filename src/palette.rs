@@ -0,0 +1,199 @@
+use super::grid::Pos;
+use super::maze;
+use super::highmap;
+
+
+/// Red, green, blue and alpha components between 0 and 1, the same layout
+/// as `graphics::types::Color`.
+pub type Rgba = [f32; 4];
+
+
+const ROOT_COLORS : [&str; 5] = [
+    "1B5E20", // green
+    "0D47A1", // blue
+    "E65100", // orange
+    "b71c1c", // red
+    "004D40" // turquoise
+];
+
+/// Turn of the hue between two groups, the fractional part of the golden
+/// ratio keeps every new hue away from the previous ones.
+const GROUP_HUE_STEP : f64 = 0.618_033_988_749_895;
+
+
+pub const DIST_PER_COLOR : usize = 80;
+
+pub const BLACK : Rgba = [0.0, 0.0, 0.0, 1.0];
+pub const WHITE : Rgba = [1.0, 1.0, 1.0, 1.0];
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Reads a `RRGGBB` or `RRGGBBAA` colour, with or without a leading `#`.
+pub fn parse_hex(hex: &str) -> Option<Rgba> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 && hex.len() != 8 {
+        return None;
+    }
+
+    let mut color = WHITE;
+    for (index, component) in color.iter_mut().enumerate().take(hex.len() / 2) {
+        let digits = hex.get(index * 2..index * 2 + 2)?;
+        let value = u8::from_str_radix(digits, 16).ok()?;
+        *component = value as f32 / 255.0;
+    }
+
+    Some(color)
+}
+
+
+/// Same as `parse_hex`, for the colours known at compile time.
+pub fn hex(hex: &str) -> Rgba {
+    parse_hex(hex).expect("valid hexadecimal colour")
+}
+
+
+/// Writes the `#rrggbb` part of a colour, components are clamped since tints
+/// may push them over 1.
+pub fn to_hex(color: Rgba) -> String {
+    let byte = |component: f32| (component.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", byte(color[0]), byte(color[1]), byte(color[2]))
+}
+
+
+/// Multiplies red, green and blue by `f`, like `graphics::Colored::tint`.
+pub fn tint(color: Rgba, f: f32) -> Rgba {
+    [color[0] * f, color[1] * f, color[2] * f, color[3]]
+}
+
+
+/// Colour of a cell `altitude` steps away from the start, cycling through
+/// the root colours every `dist_per_color` steps.
+pub fn altitude_color(altitude: usize, dist_per_color: usize) -> Rgba {
+    let color_index = (altitude / dist_per_color) % ROOT_COLORS.len();
+
+    let size = dist_per_color as f64;
+    let advance = (altitude as f64 % size) / size;
+
+    let factor = 0.4 + 1.2 * advance;
+    tint(hex(ROOT_COLORS[color_index]), factor as f32)
+}
+
+
+/// Light colour of a group of cells, distinct for every group index.
+pub fn group_color(group: usize) -> Rgba {
+    let hue = (group as f64 * GROUP_HUE_STEP).fract();
+    hsv(hue, 0.4, 0.95)
+}
+
+
+/// Colour of a hue, saturation and value all between 0 and 1.
+fn hsv(hue: f64, saturation: f64, value: f64) -> Rgba {
+    let sector = hue * 6.0;
+    let chroma = value * saturation;
+    let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let base = value - chroma;
+
+    let (red, green, blue) = match sector as usize {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second)
+    };
+
+    [(red + base) as f32, (green + base) as f32, (blue + base) as f32, 1.0]
+}
+
+
+/// Colour of the cells closest to the same source, like a Voronoi diagram.
+/// The source itself, at altitude 0, is darker.
+pub fn territory_color(pos: &Pos<highmap::CellStatus>) -> Option<Rgba> {
+    match (pos.source(), pos.height()) {
+        (Some(source), Some(0)) => Some(tint(group_color(source), 0.6)),
+        (Some(source), Some(_)) => Some(group_color(source)),
+        _ => None
+    }
+}
+
+
+/// Colour of the path found by a solver, drawn over the cells.
+pub fn path_color() -> Rgba {
+    hex("2979FF")
+}
+
+
+/// Colour of the marks left by Trémaux's algorithm on the passages.
+pub fn mark_color() -> Rgba {
+    hex("C2185B")
+}
+
+
+/// Colour of the cells reached by one of the two searches of a
+/// bidirectional solver, `side` 0 searching from the start.
+pub fn frontier_color(side: usize) -> Rgba {
+    if side == 0 { hex("81D4FA") } else { hex("A5D6A7") }
+}
+
+
+/// Colour of the cell where the two searches of a bidirectional solver met.
+pub fn meeting_color() -> Rgba {
+    hex("AA00FF")
+}
+
+
+/// Colour showing what an algorithm is doing with a cell, if anything.
+pub fn status_color(pos: &Pos<maze::CellStatus>) -> Option<Rgba> {
+    if pos.is_meeting() {
+        Some(meeting_color())
+    } else if pos.is_current() {
+        Some(hex("FF5722"))
+    } else if pos.is_active() {
+        Some(hex("FFC107"))
+    } else if pos.is_filled() {
+        Some(hex("78909C"))
+    } else if let Some(side) = pos.frontier() {
+        Some(frontier_color(side))
+    } else if let Some(group) = pos.group() {
+        Some(group_color(group))
+    } else if pos.is_visited() {
+        Some(hex("9E9E9E"))
+    } else {
+        None
+    }
+}
+
+
+/// Layers shown on the cells an algorithm has nothing to say about.
+#[derive(Clone, Copy, Debug)]
+pub struct CellFill {
+    pub highmap: bool,
+    pub voronoi: bool,
+    pub dist_per_color: usize
+}
+
+
+impl Default for CellFill {
+    fn default() -> CellFill {
+        CellFill { highmap: false, voronoi: false, dist_per_color: DIST_PER_COLOR }
+    }
+}
+
+
+/// Colour of a cell: its status first, then the territory of its source
+/// and last its altitude, as far as `fill` shows them.
+pub fn cell_color(pos: &Pos<maze::CellStatus>,
+                  hpos: Option<&Pos<highmap::CellStatus>>,
+                  fill: &CellFill) -> Option<Rgba> {
+    let territory = || if fill.voronoi { hpos.and_then(territory_color) } else { None };
+    let altitude = || if fill.highmap {
+        hpos.and_then(|hpos| hpos.height())
+            .map(|height| altitude_color(height, fill.dist_per_color))
+    } else {
+        None
+    };
+
+    status_color(pos).or_else(territory).or_else(altitude)
+}
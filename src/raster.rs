@@ -0,0 +1,258 @@
+extern crate png;
+
+use std::io::Write;
+
+use self::png::HasParameters;
+
+use super::grid::Within;
+use super::maze::OrthoMaze;
+use super::highmap::OrthoHighMap;
+use super::palette::{self, CellFill, Rgba};
+use super::layout::Layout;
+
+
+/// RGBA image of 8 bits per component, rows from top to bottom.
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>
+}
+
+
+impl Raster {
+    pub fn new(width: usize, height: usize, background: Option<Rgba>) -> Raster {
+        let color = background.unwrap_or([0.0, 0.0, 0.0, 0.0]);
+        let pixel = [byte(color[0]), byte(color[1]), byte(color[2]), byte(color[3])];
+
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&pixel);
+        }
+
+        Raster { width, height, pixels }
+    }
+
+    /// Blends `color` over the rectangle, partially covered pixels on the
+    /// edges get a proportional part of it.
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: f64, height: f64, color: Rgba) {
+        let (left, right) = (x.max(0.0), (x + width).min(self.width as f64));
+        let (top, bottom) = (y.max(0.0), (y + height).min(self.height as f64));
+        if left >= right || top >= bottom {
+            return;
+        }
+
+        for py in top.floor() as usize..bottom.ceil() as usize {
+            let cover_y = (bottom.min(py as f64 + 1.0) - top.max(py as f64)).max(0.0);
+
+            for px in left.floor() as usize..right.ceil() as usize {
+                let cover_x = (right.min(px as f64 + 1.0) - left.max(px as f64)).max(0.0);
+                self.blend(px, py, color, cover_x * cover_y);
+            }
+        }
+    }
+
+    fn blend(&mut self, x: usize, y: usize, color: Rgba, coverage: f64) {
+        let alpha = color[3].clamp(0.0, 1.0) * coverage as f32;
+        if alpha <= 0.0 {
+            return;
+        }
+
+        let index = (y * self.width + x) * 4;
+        let pixel = &mut self.pixels[index..index + 4];
+
+        let under_alpha = pixel[3] as f32 / 255.0;
+        let out_alpha = alpha + under_alpha * (1.0 - alpha);
+
+        for component in 0..3 {
+            let under = pixel[component] as f32 / 255.0;
+            let over = color[component].clamp(0.0, 1.0);
+            let mixed = (over * alpha + under * under_alpha * (1.0 - alpha)) / out_alpha;
+            pixel[component] = byte(mixed);
+        }
+        pixel[3] = byte(out_alpha);
+    }
+
+    pub fn write_png<W: Write>(&self, w: W) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        self.write_png(&mut data)
+            .expect("encoding a png in memory");
+        data
+    }
+}
+
+
+fn byte(component: f32) -> u8 {
+    (component.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Software counterpart of `StaticMazeRenderer`, drawing in a `Raster`
+/// without any GL context.
+pub struct RasterMazeRenderer {
+    layout: Layout,
+    hori_line: Rgba,
+    vert_line: Rgba,
+    background: Option<Rgba>,
+    fill: CellFill
+}
+
+
+impl Default for RasterMazeRenderer {
+    fn default() -> RasterMazeRenderer {
+        RasterMazeRenderer::new()
+    }
+}
+
+
+impl RasterMazeRenderer {
+    pub fn new() -> RasterMazeRenderer {
+        RasterMazeRenderer {
+            layout: Layout::new(10.0, 1.0),
+            hori_line: palette::BLACK,
+            vert_line: palette::BLACK,
+            background: Some(palette::WHITE),
+            fill: CellFill::default()
+        }
+    }
+
+    pub fn set_cell_size(&mut self, cell_size: f64) {
+        self.layout.cell_size = cell_size;
+    }
+
+    pub fn set_line_thickness(&mut self, line_thickness: f64) {
+        self.layout.line_thickness = line_thickness;
+    }
+
+    pub fn set_line_colors(&mut self, hori_line: Rgba, vert_line: Rgba) {
+        self.hori_line = hori_line;
+        self.vert_line = vert_line;
+    }
+
+    pub fn set_background(&mut self, background: Option<Rgba>) {
+        self.background = background;
+    }
+
+    pub fn set_cell_fill(&mut self, fill: CellFill) {
+        self.fill = fill;
+    }
+
+    /// Width and height in pixels of the rasters of `maze`.
+    pub fn frame_size(&self, maze: &OrthoMaze) -> (usize, usize) {
+        let (_, _, width, height) = self.layout
+            .frame_box(maze.grid().columns(), maze.grid().lines());
+
+        (width.ceil() as usize, height.ceil() as usize)
+    }
+
+    pub fn render(&self, maze: &OrthoMaze, highmap: &OrthoHighMap) -> Raster {
+        let (width, height) = self.frame_size(maze);
+        let mut raster = Raster::new(width, height, self.background);
+
+        self.draw_cells(maze, highmap, &mut raster);
+        self.draw_path(maze, &mut raster);
+        self.draw_frame(maze, &mut raster);
+        self.draw_gates(maze, &mut raster);
+        self.draw_marks(maze, &mut raster);
+
+        raster
+    }
+
+    /// Upper left corner of the cell in pixels, on the middle of its walls.
+    fn corner(&self, maze: &OrthoMaze, column: usize, line: usize) -> (f64, f64) {
+        let columns = maze.grid().columns();
+        let lines = maze.grid().lines();
+
+        let (origin_x, origin_y, _, _) = self.layout.frame_box(columns, lines);
+        let (offset_x, offset_y) = self.layout.image_offset(columns, lines);
+        let space = self.layout.space();
+
+        (offset_x + origin_x + column as f64 * space,
+         offset_y + origin_y + line as f64 * space)
+    }
+
+    fn draw_cells(&self, maze: &OrthoMaze, highmap: &OrthoHighMap, raster: &mut Raster) {
+        let hlt = self.layout.line_thickness * 0.5;
+        let space = self.layout.space();
+
+        for address in maze.grid().crumbs() {
+            let pos = address.from(maze).expect("position of maze exists");
+
+            let hpos = address.from(highmap);
+            let color = palette::cell_color(&pos, hpos.as_ref(), &self.fill);
+
+            if let Some(color) = color {
+                let (corner_x, corner_y) = self.corner(maze, address.column, address.line);
+                raster.fill_rect(corner_x - hlt, corner_y - hlt, space, space, color);
+            }
+        }
+    }
+
+    fn draw_path(&self, maze: &OrthoMaze, raster: &mut Raster) {
+        let path_color = palette::path_color();
+
+        for pos in maze.grid().iter().filter(|pos| pos.is_on_path()) {
+            let (corner_x, corner_y) = self.corner(maze, pos.column, pos.line);
+
+            let boxes = self.layout.path_boxes(corner_x, corner_y,
+                                               pos.path_goes_right(), pos.path_goes_down());
+            for (x, y, width, height) in boxes {
+                raster.fill_rect(x, y, width, height, path_color);
+            }
+        }
+    }
+
+    fn draw_marks(&self, maze: &OrthoMaze, raster: &mut Raster) {
+        let mark_color = palette::mark_color();
+
+        for pos in maze.grid().iter() {
+            let (corner_x, corner_y) = self.corner(maze, pos.column, pos.line);
+
+            let mut boxes = self.layout.mark_boxes(corner_x, corner_y, false, pos.marks_right());
+            boxes.extend(self.layout.mark_boxes(corner_x, corner_y, true, pos.marks_down()));
+            for (x, y, width, height) in boxes {
+                raster.fill_rect(x, y, width, height, mark_color);
+            }
+        }
+    }
+
+    fn draw_frame(&self, maze: &OrthoMaze, raster: &mut Raster) {
+        let lt = self.layout.line_thickness;
+        let (_, _, width, height) = self.layout
+            .frame_box(maze.grid().columns(), maze.grid().lines());
+
+        // top and left, the cells close the bottom and the right
+        raster.fill_rect(0.0, 0.0, width, lt, self.hori_line);
+        raster.fill_rect(0.0, 0.0, lt, height, self.vert_line);
+    }
+
+    fn draw_gates(&self, maze: &OrthoMaze, raster: &mut Raster) {
+        let lt = self.layout.line_thickness;
+        let hlt = lt * 0.5;
+        let space = self.layout.space();
+
+        for pos in maze.grid().iter() {
+            let (corner_x, corner_y) = self.corner(maze, pos.column, pos.line);
+
+            if !pos.can_move_down() {
+                raster.fill_rect(corner_x - hlt, corner_y + space - hlt,
+                                 space + lt, lt, self.hori_line);
+            }
+
+            if !pos.can_move_right() {
+                raster.fill_rect(corner_x + space - hlt, corner_y - hlt,
+                                 lt, space + lt, self.vert_line);
+            }
+        }
+    }
+}
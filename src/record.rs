@@ -0,0 +1,165 @@
+extern crate gif;
+
+use std::io::{self, Write};
+use std::collections::HashMap;
+use std::borrow::Cow;
+
+use self::gif::SetParameter;
+
+use super::maze::OrthoMaze;
+use super::highmap::OrthoHighMap;
+use super::raster::{Raster, RasterMazeRenderer};
+use super::task::{Executor, Status};
+use super::algo::base::Args;
+
+
+/// Delay between frames, in hundredths of a second.
+const FRAME_DELAY : u16 = 4;
+
+/// How long the last frame stays before the animation loops.
+const FINAL_DELAY : u16 = 200;
+
+
+/// Encodes the progress of the tasks of an `Executor` as an animated GIF,
+/// taking a frame every few steps.
+pub struct GifRecorder<W: Write> {
+    encoder: gif::Encoder<W>,
+    renderer: RasterMazeRenderer,
+    width: u16,
+    height: u16,
+    every: usize,
+    delay: u16
+}
+
+
+impl<W: Write> GifRecorder<W> {
+    /// Starts the GIF, its size is the one of `maze` drawn by `renderer`.
+    pub fn new(w: W, renderer: RasterMazeRenderer, maze: &OrthoMaze)
+        -> io::Result<GifRecorder<W>>
+    {
+        let (width, height) = renderer.frame_size(maze);
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "maze too large for a gif"));
+        }
+        let (width, height) = (width as u16, height as u16);
+
+        let mut encoder = gif::Encoder::new(w, width, height, &[])?;
+        encoder.set(gif::Repeat::Infinite)?;
+
+        Ok(GifRecorder {
+            encoder,
+            renderer,
+            width,
+            height,
+            every: 1,
+            delay: FRAME_DELAY
+        })
+    }
+
+    /// Takes a frame every `steps` steps of the executor.
+    pub fn set_every(&mut self, steps: usize) {
+        self.every = steps.max(1);
+    }
+
+    /// Shows every frame for `centiseconds` hundredths of a second.
+    pub fn set_delay(&mut self, centiseconds: u16) {
+        self.delay = centiseconds;
+    }
+
+    /// Adds a frame showing `maze` and `highmap` for `delay` hundredths of
+    /// a second.
+    pub fn capture(&mut self, maze: &OrthoMaze, highmap: &OrthoHighMap, delay: u16)
+        -> io::Result<()>
+    {
+        let raster = self.renderer.render(maze, highmap);
+        let mut frame = self.index_frame(&raster);
+        frame.delay = delay;
+
+        self.encoder.write_frame(&frame)
+    }
+
+    /// Runs all the tasks of `tasks` like `Executor::run` does, recording
+    /// the maze along the way.
+    pub fn record(&mut self, tasks: &mut Executor<Args>, args: Args) -> io::Result<Status> {
+        self.capture_args(&args, self.delay)?;
+
+        let mut steps = 0;
+        while !tasks.is_empty() {
+            let status = tasks.run_step(args.clone());
+            steps += 1;
+
+            if let Status::Aborted(why) = status {
+                self.capture_args(&args, FINAL_DELAY)?;
+                return Ok(Status::Aborted(why));
+            }
+
+            if steps % self.every == 0 && !tasks.is_empty() {
+                self.capture_args(&args, self.delay)?;
+            }
+        }
+
+        self.capture_args(&args, FINAL_DELAY)?;
+        Ok(Status::Done)
+    }
+
+    fn capture_args(&mut self, args: &Args, delay: u16) -> io::Result<()> {
+        let maze = args.maze.borrow();
+        let highmap = args.highmap.borrow();
+
+        self.capture(&maze, &highmap, delay)
+    }
+
+    /// Turns the raster into a frame of the size of the GIF, with an exact
+    /// palette when it has few enough colours.
+    fn index_frame(&self, raster: &Raster) -> gif::Frame<'static> {
+        let (width, height) = (self.width as usize, self.height as usize);
+
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for line in 0..height {
+            for column in 0..width {
+                if line < raster.height && column < raster.width {
+                    let index = (line * raster.width + column) * 4;
+                    pixels.extend_from_slice(&raster.pixels[index..index + 4]);
+                } else {
+                    pixels.extend_from_slice(&[0, 0, 0, 0]);
+                }
+            }
+        }
+
+        let mut colors : HashMap<[u8; 4], u8> = HashMap::new();
+        let mut palette = Vec::new();
+        let mut buffer = Vec::with_capacity(width * height);
+
+        for pixel in pixels.chunks(4) {
+            let key = if pixel[3] == 0 {
+                [0, 0, 0, 0]
+            } else {
+                [pixel[0], pixel[1], pixel[2], 0xFF]
+            };
+
+            let next = colors.len();
+            if next > 255 && !colors.contains_key(&key) {
+                let mut frame = gif::Frame::from_rgba(self.width, self.height, &mut pixels);
+                frame.dispose = gif::DisposalMethod::Background;
+                return frame;
+            }
+
+            let index = *colors.entry(key).or_insert_with(|| {
+                palette.extend_from_slice(&key[..3]);
+                next as u8
+            });
+            buffer.push(index);
+        }
+
+        gif::Frame {
+            width: self.width,
+            height: self.height,
+            dispose: gif::DisposalMethod::Background,
+            transparent: colors.get(&[0, 0, 0, 0]).cloned(),
+            palette: Some(palette),
+            buffer: Cow::Owned(buffer),
+            ..gif::Frame::default()
+        }
+    }
+}
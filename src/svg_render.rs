@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use super::grid::Within;
+use super::maze::OrthoMaze;
+use super::highmap::OrthoHighMap;
+use super::palette::{self, CellFill, Rgba};
+use super::layout::Layout;
+
+
+/// Writes the maze as an SVG document, drawing the same walls, frame and
+/// cell fills as `StaticMazeRenderer`.
+pub struct SvgMazeRenderer {
+    layout: Layout,
+    hori_line: Rgba,
+    vert_line: Rgba,
+    background: Option<Rgba>,
+    fill: CellFill
+}
+
+
+impl Default for SvgMazeRenderer {
+    fn default() -> SvgMazeRenderer {
+        SvgMazeRenderer::new()
+    }
+}
+
+
+impl SvgMazeRenderer {
+    pub fn new() -> SvgMazeRenderer {
+        SvgMazeRenderer {
+            layout: Layout::new(10.0, 1.0),
+            hori_line: palette::BLACK,
+            vert_line: palette::BLACK,
+            background: Some(palette::WHITE),
+            fill: CellFill::default()
+        }
+    }
+
+    pub fn set_cell_size(&mut self, cell_size: f64) {
+        self.layout.cell_size = cell_size;
+    }
+
+    pub fn set_line_thickness(&mut self, line_thickness: f64) {
+        self.layout.line_thickness = line_thickness;
+    }
+
+    pub fn set_line_colors(&mut self, hori_line: Rgba, vert_line: Rgba) {
+        self.hori_line = hori_line;
+        self.vert_line = vert_line;
+    }
+
+    pub fn set_background(&mut self, background: Option<Rgba>) {
+        self.background = background;
+    }
+
+    pub fn set_cell_fill(&mut self, fill: CellFill) {
+        self.fill = fill;
+    }
+
+    pub fn render(&self, maze: &OrthoMaze, highmap: &OrthoHighMap) -> String {
+        let (width, height) = self.frame_size(maze);
+        let mut svg = String::new();
+
+        writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+                       width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+                 w = width, h = height).unwrap();
+
+        if let Some(background) = self.background {
+            writeln!(svg, "<rect width=\"{}\" height=\"{}\" {}/>",
+                     width, height, Self::paint("fill", background)).unwrap();
+        }
+
+        self.write_cells(maze, highmap, &mut svg);
+        self.write_path(maze, &mut svg);
+        self.write_walls(maze, &mut svg);
+        self.write_marks(maze, &mut svg);
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn frame_size(&self, maze: &OrthoMaze) -> (f64, f64) {
+        let (_, _, width, height) = self.layout
+            .frame_box(maze.grid().columns(), maze.grid().lines());
+
+        (width, height)
+    }
+
+    /// Upper left corner of the cell, on the middle of its walls.
+    fn corner(&self, column: usize, line: usize) -> (f64, f64) {
+        let hlt = self.layout.line_thickness * 0.5;
+        let space = self.layout.space();
+
+        (hlt + column as f64 * space, hlt + line as f64 * space)
+    }
+
+    fn write_cells(&self, maze: &OrthoMaze, highmap: &OrthoHighMap, svg: &mut String) {
+        let hlt = self.layout.line_thickness * 0.5;
+        let space = self.layout.space();
+
+        svg.push_str("<g shape-rendering=\"crispEdges\">\n");
+
+        for address in maze.grid().crumbs() {
+            let pos = address.from(maze).expect("position of maze exists");
+
+            let hpos = address.from(highmap);
+            let color = palette::cell_color(&pos, hpos.as_ref(), &self.fill);
+
+            if let Some(color) = color {
+                let (corner_x, corner_y) = self.corner(address.column, address.line);
+                writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+                         corner_x - hlt, corner_y - hlt, space, space,
+                         Self::paint("fill", color)).unwrap();
+            }
+        }
+
+        svg.push_str("</g>\n");
+    }
+
+    fn write_path(&self, maze: &OrthoMaze, svg: &mut String) {
+        let mut path = String::new();
+
+        for pos in maze.grid().iter().filter(|pos| pos.is_on_path()) {
+            let (corner_x, corner_y) = self.corner(pos.column, pos.line);
+
+            let boxes = self.layout.path_boxes(corner_x, corner_y,
+                                               pos.path_goes_right(), pos.path_goes_down());
+            for (x, y, width, height) in boxes {
+                write!(path, "M{} {}h{}v{}h{}z", x, y, width, height, -width).unwrap();
+            }
+        }
+
+        if !path.is_empty() {
+            writeln!(svg, "<path d=\"{}\" {}/>",
+                     path, Self::paint("fill", palette::path_color())).unwrap();
+        }
+    }
+
+    fn write_marks(&self, maze: &OrthoMaze, svg: &mut String) {
+        let mut marks = String::new();
+
+        for pos in maze.grid().iter() {
+            let (corner_x, corner_y) = self.corner(pos.column, pos.line);
+
+            let mut boxes = self.layout.mark_boxes(corner_x, corner_y, false, pos.marks_right());
+            boxes.extend(self.layout.mark_boxes(corner_x, corner_y, true, pos.marks_down()));
+            for (x, y, width, height) in boxes {
+                write!(marks, "M{} {}h{}v{}h{}z", x, y, width, height, -width).unwrap();
+            }
+        }
+
+        if !marks.is_empty() {
+            writeln!(svg, "<path d=\"{}\" {}/>",
+                     marks, Self::paint("fill", palette::mark_color())).unwrap();
+        }
+    }
+
+    fn write_walls(&self, maze: &OrthoMaze, svg: &mut String) {
+        let hlt = self.layout.line_thickness * 0.5;
+        let space = self.layout.space();
+        let (width, height) = self.frame_size(maze);
+
+        // top and left of the frame, the cells close the bottom and the right
+        let mut hori = format!("M{} {}H{}", 0, hlt, width);
+        let mut vert = format!("M{} {}V{}", hlt, 0, height);
+
+        for pos in maze.grid().iter() {
+            let (corner_x, corner_y) = self.corner(pos.column, pos.line);
+
+            if !pos.can_move_down() {
+                write!(hori, "M{} {}H{}",
+                       corner_x - hlt, corner_y + space, corner_x + space + hlt).unwrap();
+            }
+
+            if !pos.can_move_right() {
+                write!(vert, "M{} {}V{}",
+                       corner_x + space, corner_y - hlt, corner_y + space + hlt).unwrap();
+            }
+        }
+
+        writeln!(svg, "<path d=\"{}\" fill=\"none\" stroke-width=\"{}\" {}/>",
+                 hori, self.layout.line_thickness, Self::paint("stroke", self.hori_line)).unwrap();
+        writeln!(svg, "<path d=\"{}\" fill=\"none\" stroke-width=\"{}\" {}/>",
+                 vert, self.layout.line_thickness, Self::paint("stroke", self.vert_line)).unwrap();
+    }
+
+    /// Attributes painting `attribute` (fill or stroke) with `color`.
+    fn paint(attribute: &str, color: Rgba) -> String {
+        if color[3] < 1.0 {
+            format!("{a}=\"{}\" {a}-opacity=\"{}\"",
+                    palette::to_hex(color), color[3].max(0.0), a = attribute)
+        } else {
+            format!("{}=\"{}\"", attribute, palette::to_hex(color))
+        }
+    }
+}
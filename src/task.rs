@@ -25,17 +25,23 @@ pub trait Task<T> {
 
 
 pub struct Executor<T> {
-    stack: VecDeque<Box<Task<T>>>
+    stack: VecDeque<Box<Task<T>>>,
+    verbose: bool
 }
 
 
 impl<T> Executor<T> {
     pub fn new() -> Executor<T> {
         Executor{
-            stack: VecDeque::new()
+            stack: VecDeque::new(),
+            verbose: DEBUG_ALGO
         }
     }
 
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
     pub fn stack(&mut self, task: Box<Task<T>>) {
         self.stack.push_back(task);
     }
@@ -43,6 +49,10 @@ impl<T> Executor<T> {
     pub fn clear(&mut self) {
         self.stack.clear();
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
     
     
     fn do_exec(&mut self, mut args: &mut T) -> Status {
@@ -60,29 +70,30 @@ impl<T> Executor<T> {
     }
 
 
-    pub fn run(&mut self, mut args: T) {
-        'all: while !self.stack.is_empty() {
-            if let Status::Aborted(_) = self.do_exec(&mut args) {
-                break 'all;
+    pub fn run(&mut self, mut args: T) -> Status {
+        while !self.stack.is_empty() {
+            if let Status::Aborted(why) = self.do_exec(&mut args) {
+                return Status::Aborted(why);
             }
         }
+        Status::Done
     }
 
     pub fn run_task(&mut self, mut args: T) {
         while self.do_exec(&mut args) == Status::Continuing {}
     }
 
-    pub fn run_step(&mut self, mut args: T) {
-        self.do_exec(&mut args);
+    pub fn run_step(&mut self, mut args: T) -> Status {
+        self.do_exec(&mut args)
     }
 
     fn execute_task(&mut self, mut args: &mut T) -> Option<Status> {
         if let Some(ref mut task) = self.stack.front_mut() {
-            Self::try_log(task, task.context());
+            Self::try_log(self.verbose, task, task.context());
             
             let status = task.execute_one(&mut args);
             
-            Self::try_log(task, task.action());
+            Self::try_log(self.verbose, task, task.action());
             
             match status {
                 Status::Done => Self::log(task, "Done"),
@@ -100,11 +111,11 @@ impl<T> Executor<T> {
     }
 
     fn log(task: &Box<Task<T>>, msg: &str) {
-        println!("[{}] {}", task.name(), msg);
+        eprintln!("[{}] {}", task.name(), msg);
     }
 
-    fn try_log<'a>(task: &Box<Task<T>>, msg: Option<Cow<'a, str>>) {
-        if DEBUG_ALGO {
+    fn try_log<'a>(verbose: bool, task: &Box<Task<T>>, msg: Option<Cow<'a, str>>) {
+        if verbose {
             if let Some(msg) = msg {
                 match msg {
                     Cow::Borrowed(text) => Self::log(task, text),
@@ -0,0 +1,267 @@
+use std;
+use std::fmt;
+
+use super::maze::OrthoMaze;
+
+
+/// Location and reason of the first problem met while parsing a maze,
+/// `line` and `column` start at 1 like in text editors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: &'static str
+}
+
+
+impl ParseError {
+    fn new(line: usize, column: usize, reason: &'static str) -> ParseError {
+        ParseError { line: line + 1, column: column + 1, reason }
+    }
+}
+
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.reason)
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+/// Rebuilds an `OrthoMaze` from the ASCII grid written by `TextMazeRenderer`:
+///
+/// ```text
+/// +--+--+
+/// |     |
+/// +  +--+
+/// |     |
+/// +--+--+
+/// ```
+///
+/// The cell width is inferred from the first line, the content of the cells
+/// (depth digits, status markers) is ignored.
+pub fn parse(text: &str) -> Result<OrthoMaze, ParseError> {
+    let rows : Vec<Vec<char>> = text.lines()
+        .map(|row| row.trim_end_matches('\r').chars().collect())
+        .collect();
+    let row_count = rows.iter()
+        .rposition(|row| !row.is_empty())
+        .map(|last| last + 1)
+        .unwrap_or(0);
+    let rows = &rows[..row_count];
+
+    if rows.is_empty() {
+        return Err(ParseError::new(0, 0, "empty maze"));
+    }
+
+    let width = cell_width(&rows[0])?;
+    let columns = (rows[0].len() - 1) / (width + 1);
+
+    if row_count < 3 {
+        return Err(ParseError::new(row_count, 0, "missing cell line"));
+    } else if row_count % 2 == 0 {
+        return Err(ParseError::new(row_count, 0, "missing closing wall line"));
+    }
+
+    let lines = (row_count - 1) / 2;
+    let mut maze = OrthoMaze::new(columns, lines);
+
+    for (index, row) in rows.iter().enumerate() {
+        if row.len() != rows[0].len() {
+            let column = std::cmp::min(row.len(), rows[0].len());
+            return Err(ParseError::new(index, column, "line length differs from the first line"));
+        }
+
+        if index % 2 == 0 {
+            let is_border = index == 0 || index + 1 == row_count;
+            parse_walls(&mut maze, row, index, width, is_border)?;
+        } else {
+            parse_cells(&mut maze, row, index, width)?;
+        }
+    }
+
+    Ok(maze)
+}
+
+
+fn cell_width(row: &[char]) -> Result<usize, ParseError> {
+    if row.first() != Some(&'+') {
+        return Err(ParseError::new(0, 0, "expected '+' to open the maze"));
+    }
+
+    let width = row.iter()
+        .skip(1)
+        .position(|c| *c == '+')
+        .ok_or_else(|| ParseError::new(0, row.len(), "expected '+' to close the first cell"))?;
+
+    if width == 0 {
+        return Err(ParseError::new(0, 1, "cells must be at least one character wide"));
+    } else if !(row.len() - 1).is_multiple_of(width + 1) {
+        return Err(ParseError::new(0, row.len(), "line does not end on a cell boundary"));
+    }
+
+    Ok(width)
+}
+
+
+/// Reads the walls above the maze line `row_index / 2`.
+fn parse_walls(maze: &mut OrthoMaze,
+               row: &[char],
+               row_index: usize,
+               width: usize,
+               is_border: bool)
+    -> Result<(), ParseError>
+{
+    let line = row_index / 2;
+
+    for (column, chunk) in row.chunks(width + 1).enumerate() {
+        let offset = column * (width + 1);
+
+        if chunk[0] != '+' {
+            return Err(ParseError::new(row_index, offset, "expected '+' on a cell corner"));
+        }
+
+        let segment = &chunk[1..];
+        if segment.is_empty() {
+            continue;
+        }
+
+        if segment.iter().all(|c| *c == '-') {
+            continue;
+        } else if !segment.iter().all(|c| *c == ' ') {
+            let bad = match segment[0] {
+                '-' | ' ' => segment.iter().position(|c| *c != segment[0]).unwrap_or(0),
+                _ => 0
+            };
+            return Err(ParseError::new(row_index, offset + 1 + bad,
+                                       "wall must be either all '-' or all blank"));
+        } else if is_border {
+            return Err(ParseError::new(row_index, offset + 1, "opening in the outer wall"));
+        }
+
+        maze.carve(column, line - 1, column, line);
+    }
+
+    Ok(())
+}
+
+
+/// Reads the walls between the cells of the maze line `row_index / 2`.
+fn parse_cells(maze: &mut OrthoMaze, row: &[char], row_index: usize, width: usize)
+    -> Result<(), ParseError>
+{
+    let line = row_index / 2;
+    let columns = (row.len() - 1) / (width + 1);
+
+    for column in 0..columns + 1 {
+        let offset = column * (width + 1);
+
+        match row[offset] {
+            '|' => {}
+            ' ' if column == 0 || column == columns => {
+                return Err(ParseError::new(row_index, offset, "opening in the outer wall"));
+            }
+            ' ' => maze.carve(column - 1, line, column, line),
+            _ => {
+                return Err(ParseError::new(row_index, offset, "expected '|' or blank between cells"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::grid::Within;
+    use super::super::highmap::OrthoHighMap;
+    use super::super::text_render::TextMazeRenderer;
+
+    fn sample_maze() -> OrthoMaze {
+        let mut maze = OrthoMaze::new(4, 3);
+        for column in 0..3 {
+            maze.carve(column, 0, column + 1, 0);
+        }
+        for column in 0..4 {
+            maze.carve(column, 0, column, 1);
+        }
+        maze.carve(1, 1, 1, 2);
+        maze.carve(0, 2, 1, 2);
+        maze.carve(2, 2, 3, 2);
+        maze.carve(3, 1, 3, 2);
+        maze
+    }
+
+    fn gates(maze: &OrthoMaze) -> Vec<(bool, bool)> {
+        maze.grid().iter()
+            .map(|pos| (pos.can_move_right(), pos.can_move_down()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_the_rendered_maze_back() {
+        let maze = sample_maze();
+        let highmap = OrthoHighMap::new(4, 3);
+        let text = TextMazeRenderer::new().render(&maze, &highmap);
+
+        let parsed = parse(&text).expect("rendered maze parses");
+        assert_eq!(parsed.grid().columns(), 4);
+        assert_eq!(parsed.grid().lines(), 3);
+        assert_eq!(gates(&parsed), gates(&maze));
+    }
+
+    #[test]
+    fn ignores_the_depths_in_wider_cells() {
+        let maze = sample_maze();
+        let mut highmap = OrthoHighMap::new(4, 3);
+        for (index, address) in maze.grid().crumbs().enumerate() {
+            if let Some(mut pos) = address.from_mut(&mut highmap) {
+                pos.set_depth(index * 100);
+            }
+        }
+        highmap.highest = 1100;
+
+        let mut renderer = TextMazeRenderer::new();
+        renderer.toggle_highmap();
+        let text = renderer.render(&maze, &highmap);
+        assert!(text.contains("1100"));
+
+        let parsed = parse(&text).expect("rendered maze with depths parses");
+        assert_eq!(gates(&parsed), gates(&maze));
+    }
+
+    #[test]
+    fn reports_where_a_line_is_too_short() {
+        let text = "+--+--+\n|     |\n+--+\n";
+        let err = parse(text).err().expect("short line is refused");
+
+        assert_eq!((err.line, err.column), (3, 5));
+        assert_eq!(err.reason, "line length differs from the first line");
+    }
+
+    #[test]
+    fn reports_where_a_wall_is_broken() {
+        let text = "+--+--+\n|  |  |\n+--+- +\n";
+        let err = parse(text).err().expect("broken wall is refused");
+
+        assert_eq!((err.line, err.column), (3, 6));
+    }
+
+    #[test]
+    fn reports_an_opening_in_the_outer_wall() {
+        let text = "+--+--+\n   |  |\n+--+--+\n";
+        let err = parse(text).err().expect("opening is refused");
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.reason, "opening in the outer wall");
+    }
+}
@@ -0,0 +1,253 @@
+use std;
+
+use super::grid::Within;
+use super::maze::OrthoMaze;
+use super::highmap::OrthoHighMap;
+
+
+const CELL_WIDTH : usize = 2;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Charset {
+    Ascii,
+    Unicode
+}
+
+
+impl Charset {
+    pub fn from_name(name: &str) -> Option<Charset> {
+        match name.to_lowercase().as_str() {
+            "ascii" => Some(Charset::Ascii),
+            "unicode" => Some(Charset::Unicode),
+            _ => None
+        }
+    }
+
+    fn horizontal(&self) -> char {
+        match *self {
+            Charset::Ascii => '-',
+            Charset::Unicode => '─'
+        }
+    }
+
+    fn vertical(&self) -> char {
+        match *self {
+            Charset::Ascii => '|',
+            Charset::Unicode => '│'
+        }
+    }
+
+    fn path(&self) -> char {
+        match *self {
+            Charset::Ascii => 'o',
+            Charset::Unicode => '•'
+        }
+    }
+
+    fn junction(&self, up: bool, down: bool, left: bool, right: bool) -> char {
+        if *self == Charset::Ascii {
+            return '+';
+        }
+
+        match (up, down, left, right) {
+            (false, false, false, false) => ' ',
+            (true, false, false, false) => '╵',
+            (false, true, false, false) => '╷',
+            (false, false, true, false) => '╴',
+            (false, false, false, true) => '╶',
+            (true, true, false, false) => '│',
+            (false, false, true, true) => '─',
+            (false, true, false, true) => '┌',
+            (false, true, true, false) => '┐',
+            (true, false, false, true) => '└',
+            (true, false, true, false) => '┘',
+            (true, true, false, true) => '├',
+            (true, true, true, false) => '┤',
+            (false, true, true, true) => '┬',
+            (true, false, true, true) => '┴',
+            (true, true, true, true) => '┼'
+        }
+    }
+}
+
+
+// ----------------------------------------------------------------------------
+
+
+pub struct TextMazeRenderer {
+    charset: Charset,
+    visible_highmap: bool,
+    visible_status: bool
+}
+
+
+impl Default for TextMazeRenderer {
+    fn default() -> TextMazeRenderer {
+        TextMazeRenderer::new()
+    }
+}
+
+
+impl TextMazeRenderer {
+    pub fn new() -> TextMazeRenderer {
+        TextMazeRenderer {
+            charset: Charset::Ascii,
+            visible_highmap: false,
+            visible_status: false
+        }
+    }
+
+    pub fn set_charset(&mut self, charset: Charset) {
+        self.charset = charset;
+    }
+
+    pub fn toggle_highmap(&mut self) {
+        self.visible_highmap = !self.visible_highmap;
+    }
+
+    pub fn toggle_status(&mut self) {
+        self.visible_status = !self.visible_status;
+    }
+
+    pub fn render(&self, maze: &OrthoMaze, highmap: &OrthoHighMap) -> String {
+        let columns = maze.grid().columns();
+        let lines = maze.grid().lines();
+        if columns == 0 || lines == 0 {
+            return String::new();
+        }
+
+        let width = self.cell_width(maze, highmap);
+        let mut text = String::new();
+
+        for line in 0..lines + 1 {
+            self.render_walls(maze, line, width, &mut text);
+            if line < lines {
+                self.render_cells(maze, highmap, line, width, &mut text);
+            }
+        }
+
+        text
+    }
+
+    fn cell_width(&self, maze: &OrthoMaze, highmap: &OrthoHighMap) -> usize {
+        if !self.visible_highmap {
+            return CELL_WIDTH;
+        }
+
+        maze.grid().crumbs()
+            .filter_map(|address| highmap.grid().cell(address.column, address.line))
+            .filter_map(|pos| pos.height())
+            .map(|height| height.to_string().len())
+            .fold(CELL_WIDTH, std::cmp::max)
+    }
+
+    /// Renders the walls running above the line `line`, the one below the
+    /// last line when `line` equals the line count.
+    fn render_walls(&self, maze: &OrthoMaze, line: usize, width: usize, text: &mut String) {
+        let columns = maze.grid().columns();
+
+        for column in 0..columns + 1 {
+            let up = line > 0 && Self::wall_left_of(maze, column, line - 1);
+            let down = Self::wall_left_of(maze, column, line);
+            let left = column > 0 && Self::wall_above(maze, column - 1, line);
+            let right = Self::wall_above(maze, column, line);
+
+            text.push(self.charset.junction(up, down, left, right));
+
+            if column < columns {
+                let fill = if right { self.charset.horizontal() } else { ' ' };
+                text.extend(std::iter::repeat_n(fill, width));
+            }
+        }
+
+        text.push('\n');
+    }
+
+    fn render_cells(&self,
+                    maze: &OrthoMaze,
+                    highmap: &OrthoHighMap,
+                    line: usize,
+                    width: usize,
+                    text: &mut String) {
+        let columns = maze.grid().columns();
+
+        for column in 0..columns + 1 {
+            if Self::wall_left_of(maze, column, line) {
+                text.push(self.charset.vertical());
+            } else {
+                text.push(' ');
+            }
+
+            if column < columns {
+                let content = self.cell_content(maze, highmap, column, line);
+                text.push_str(&format!("{:>1$}", content, width));
+            }
+        }
+
+        text.push('\n');
+    }
+
+    fn cell_content(&self,
+                    maze: &OrthoMaze,
+                    highmap: &OrthoHighMap,
+                    column: usize,
+                    line: usize)
+        -> String
+    {
+        if self.visible_status {
+            if let Some(pos) = maze.grid().cell(column, line) {
+                if pos.is_current() {
+                    return String::from("@");
+                } else if pos.is_active() {
+                    return String::from("*");
+                }
+            }
+        }
+
+        let on_path = maze.grid().cell(column, line)
+            .map(|pos| pos.is_on_path())
+            .unwrap_or(false);
+        if on_path {
+            return self.charset.path().to_string();
+        }
+
+        if self.visible_highmap {
+            let height = highmap.grid().cell(column, line)
+                .and_then(|pos| pos.height());
+            if let Some(height) = height {
+                return height.to_string();
+            }
+        }
+
+        String::new()
+    }
+
+    /// Tells if a wall stands on the left of the cell at `column`, `line`.
+    /// `column` may equal the column count to query the right border.
+    fn wall_left_of(maze: &OrthoMaze, column: usize, line: usize) -> bool {
+        if line >= maze.grid().lines() {
+            false
+        } else if column == 0 || column >= maze.grid().columns() {
+            true
+        } else {
+            maze.grid().cell(column - 1, line)
+                .map(|pos| !pos.can_move_right())
+                .unwrap_or(true)
+        }
+    }
+
+    /// Tells if a wall stands above the cell at `column`, `line`.
+    /// `line` may equal the line count to query the bottom border.
+    fn wall_above(maze: &OrthoMaze, column: usize, line: usize) -> bool {
+        if column >= maze.grid().columns() {
+            false
+        } else if line == 0 || line >= maze.grid().lines() {
+            true
+        } else {
+            maze.grid().cell(column, line - 1)
+                .map(|pos| !pos.can_move_down())
+                .unwrap_or(true)
+        }
+    }
+}